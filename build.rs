@@ -0,0 +1,179 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Generates the file lists of the template directories in `src/templates`,
+//! which are included by `templates::template_files!`.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[allow(dead_code, clippy::box_collection)]
+#[path = "src/templates/engine/mod.rs"]
+mod engine;
+
+/// Files with these extensions are copied verbatim, because their own `%VARIABLE%` syntax clashes with placeholders.
+const VERBATIM_EXTENSIONS: [&str; 2] = ["bat", "cmd"];
+
+/// Files that are always executable in generated projects, since the file mode isn't available everywhere, e.g. on Windows.
+const EXECUTABLE_FILES: [&str; 1] = ["gradlew"];
+
+/// The directory in `src/templates` that contains the partials for `include`.
+const PARTIALS_DIR: &str = "partials";
+
+struct TemplateFile {
+    /// The path relative to the template directory, with `/` as the separator.
+    relative_path: String,
+    source_path: PathBuf,
+    text: Option<String>,
+    executable: bool,
+}
+
+fn main() {
+    let manifest_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    let templates_dir = manifest_dir.join("src/templates");
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap()).join("templates");
+    println!("cargo:rerun-if-changed=src/templates");
+    fs::create_dir_all(&out_dir).unwrap();
+
+    let mut template_dirs: Vec<(String, Vec<TemplateFile>)> = Vec::new();
+    for entry in read_dir_sorted(&templates_dir) {
+        if !entry.is_dir() || entry.file_name().unwrap() == "engine" {
+            continue;
+        }
+
+        let name = entry.file_name().unwrap().to_str().unwrap().to_owned();
+        let mut files = Vec::new();
+        collect_files(&entry, "", &mut files);
+        template_dirs.push((name, files));
+    }
+
+    let mut partials = engine::Partials::new();
+    for (name, files) in &template_dirs {
+        if name == PARTIALS_DIR {
+            for file in files {
+                partials.insert(&file.relative_path, file.text.as_deref().unwrap_or_default());
+            }
+        }
+    }
+
+    for (name, files) in &template_dirs {
+        for file in files {
+            if let Some(text) = &file.text {
+                let result = engine::read_front_matter(text)
+                    .and(engine::read_template_with_partials(text, &partials));
+                if let Err(err) = result {
+                    panic!("Invalid template {}/{}: {}", name, file.relative_path, err);
+                }
+            }
+        }
+
+        fs::write(out_dir.join(format!("{}.rs", name)), generate_file_list(files)).unwrap();
+    }
+}
+
+fn read_dir_sorted(dir: &Path) -> Vec<PathBuf> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("Could not read {}: {}", dir.display(), err))
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    entries.sort();
+    entries
+}
+
+fn collect_files(dir: &Path, prefix: &str, files: &mut Vec<TemplateFile>) {
+    for path in read_dir_sorted(dir) {
+        let file_name = path.file_name().unwrap().to_str().unwrap();
+        let relative_path = format!("{}{}", prefix, file_name);
+
+        if path.is_dir() {
+            collect_files(&path, &format!("{}/", relative_path), files);
+            continue;
+        }
+
+        let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+        if extension == "rs" {
+            continue;
+        }
+
+        let bytes = fs::read(&path).unwrap();
+        let text = if VERBATIM_EXTENSIONS.contains(&extension) {
+            None
+        } else {
+            String::from_utf8(bytes).ok()
+        };
+
+        files.push(TemplateFile {
+            relative_path,
+            source_path: path.clone(),
+            text,
+            executable: is_executable(&path),
+        });
+    }
+}
+
+fn is_executable(path: &Path) -> bool {
+    let listed = path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| EXECUTABLE_FILES.contains(&name));
+    listed || has_executable_mode(path)
+}
+
+#[cfg(unix)]
+fn has_executable_mode(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).unwrap().permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn has_executable_mode(_path: &Path) -> bool {
+    false
+}
+
+/// Converts a template path to a Rust identifier, e.g. `src/%MOD_ID%.mixins.json` to `src_mod_id_mixins_json`.
+fn to_identifier(path: &str) -> String {
+    let mut identifier = String::new();
+    for c in path.chars() {
+        if c.is_ascii_alphanumeric() {
+            identifier.push(c.to_ascii_lowercase());
+        } else if !identifier.is_empty() && !identifier.ends_with('_') {
+            identifier.push('_');
+        }
+    }
+    identifier.trim_end_matches('_').to_owned()
+}
+
+fn generate_file_list(files: &[TemplateFile]) -> String {
+    let mut output = String::from("// Generated by build.rs from the files in this template directory.\n\n");
+    let mut fn_names: Vec<String> = Vec::new();
+
+    for file in files {
+        let fn_name = to_identifier(&file.relative_path);
+        if fn_names.contains(&fn_name) {
+            panic!("Template files with the same identifier {}", fn_name);
+        }
+
+        let macro_name = if file.text.is_some() { "file_data" } else { "binary_file_data" };
+        let permissions = if file.executable { "Execute" } else { "None" };
+        writeln!(
+            output,
+            "crate::templates::{}!({} {}, TEMPLATE_DIR, INCLUDE_DIR_IN_TARGET, {:?}, {:?}, {});",
+            macro_name,
+            fn_name.to_uppercase(),
+            fn_name,
+            file.relative_path,
+            file.source_path.to_str().unwrap(),
+            permissions,
+        )
+        .unwrap();
+        fn_names.push(fn_name);
+    }
+
+    writeln!(output, "\ncrate::templates::file_list!(pub all_files,").unwrap();
+    for fn_name in &fn_names {
+        writeln!(output, "    {}", fn_name).unwrap();
+    }
+    writeln!(output, ");").unwrap();
+    output
+}
@@ -5,13 +5,16 @@
 import init, {
     create_state,
     generate,
+    is_valid_class_name,
     is_valid_mod_id,
     list_all_minecraft_versions,
     supports_arch_api,
     supports_forge,
     supports_neoforge,
-    arch_api_supports_forge,
+    supports_parchment,
+    to_class_name,
     to_mod_id,
+    validate_class_name,
     validate_mod_id
 } from "./templateer.js";
 await init();
@@ -36,14 +39,26 @@ for (const versionMetadata of versionList.versions.reverse()) {
 const projectTypeToggles = document.getElementById("project-type-toggles").getElementsByTagName("input");
 const multiplatformInput = document.getElementById("multiplatform-input");
 const multiplatformSettings = document.getElementById("multiplatform-settings");
+const neoForgeProjectInput = document.getElementById("neoforge-project-input");
+const neoForgeSettings = document.getElementById("neoforge-settings");
 
 for (const input of projectTypeToggles) {
     input.onchange = refreshDisplayedProjectType;
 };
 
-// Add listeners to Forge checkboxes for controlling the Architectury API checkbox.
-document.getElementById("forge-loader-input").onchange = refreshArchitecturySupport;
+// ModDevGradle doesn't support Yarn, so it's disabled when Yarn is chosen.
+for (const input of document.getElementsByName("mappings")) {
+    input.onchange = refreshToolchainSupport;
+}
+
+// Add listeners to Forge checkboxes for controlling the Architectury API and Forge-like checkboxes.
+document.getElementById("forge-loader-input").onchange = () => {
+    refreshArchitecturySupport();
+    refreshForgeLikeCheckbox();
+};
+document.getElementById("neoforge-loader-input").onchange = refreshForgeLikeCheckbox;
 refreshArchitecturySupport();
+refreshForgeLikeCheckbox();
 
 // Add listeners to Fabric and Quilt checkboxes for controlling the Fabric-like checkbox,
 // and refresh the Fabric-like status according to the default state.
@@ -54,16 +69,23 @@ refreshFabricLikeCheckbox();
 // Add generated mod id placeholder when not specified manually
 const modNameInput = document.getElementById("mod-name-input");
 const modIdInput = document.getElementById("mod-id-input");
+const mainClassNameInput = document.getElementById("main-class-name-input");
 
 modNameInput.oninput = () => {
     refreshModIdPlaceholder();
     validateModId();
+    refreshMainClassNamePlaceholder();
+    validateMainClassName();
 };
 
 function refreshModIdPlaceholder() {
     modIdInput.placeholder = to_mod_id(modNameInput.value) ?? "";
 }
 
+function refreshMainClassNamePlaceholder() {
+    mainClassNameInput.placeholder = to_class_name(modNameInput.value) ?? "";
+}
+
 // Validate mod ids
 const modIdLabel = document.getElementById("mod-id-label");
 modIdInput.oninput = validateModId;
@@ -90,6 +112,32 @@ function getModId() {
     return value;
 }
 
+// Validate main class names
+const mainClassNameLabel = document.getElementById("main-class-name-label");
+mainClassNameInput.oninput = validateMainClassName;
+
+function validateMainClassName() {
+    const validation = validate_class_name(getMainClassName());
+
+    if (validation[0]) {
+        mainClassNameLabel.removeAttribute("error");
+    } else {
+        mainClassNameLabel.setAttribute("error", validation[1]);
+    }
+}
+
+function isMainClassNameValid() {
+    return is_valid_class_name(getMainClassName());
+}
+
+function getMainClassName() {
+    let value = mainClassNameInput.value;
+    if (value === "") {
+        value = mainClassNameInput.placeholder;
+    }
+    return value;
+}
+
 function getProjectType() {
     for (const input of projectTypeToggles) {
         if (input.checked) {
@@ -98,6 +146,22 @@ function getProjectType() {
     }
 }
 
+function getToolchain() {
+    for (const input of document.getElementsByName("toolchain")) {
+        if (input.checked) {
+            return input.getAttribute("toolchain");
+        }
+    }
+}
+
+function getLanguage() {
+    for (const input of document.getElementsByName("language")) {
+        if (input.checked) {
+            return input.getAttribute("language");
+        }
+    }
+}
+
 function getMappingSet() {
     for (const input of document.getElementsByTagName("input")) {
         if (input.name !== "mappings") continue;
@@ -110,15 +174,19 @@ function getMappingSet() {
 function updateState() {
     state.mod_name = modNameInput.value;
     state.mod_id = getModId();
+    state.main_class_name = getMainClassName();
     state.package_name = document.getElementById("package-input").value;
     state.game_version = mcSelect.value;
     state.project_type = getProjectType();
     state.mapping_set = getMappingSet();
+    state.toolchain = getToolchain();
+    state.language = getLanguage();
     state.subprojects.fabric = document.getElementById("fabric-loader-input").checked;
     state.subprojects.forge = document.getElementById("forge-loader-input").checked && isForgeAvailable();
     state.subprojects.neoforge = document.getElementById("neoforge-loader-input").checked && isNeoForgeAvailable();
     state.subprojects.quilt = document.getElementById("quilt-loader-input").checked;
     state.subprojects.fabric_likes = document.getElementById("fabric-like-input").checked && isFabricLikeAvailable();
+    state.subprojects.forge_likes = document.getElementById("forge-like-input").checked && isForgeLikeAvailable();
     state.dependencies.architectury_api = document.getElementById("architectury-api-input").checked && isArchitecturyApiAvailable();
 }
 
@@ -140,6 +208,21 @@ function refreshDisplayedProjectType() {
     } else {
         multiplatformSettings.classList.add("hidden");
     }
+
+    if (neoForgeProjectInput.checked) {
+        neoForgeSettings.classList.remove("hidden");
+    } else {
+        neoForgeSettings.classList.add("hidden");
+    }
+}
+
+function refreshToolchainSupport() {
+    const modDevGradleInput = document.getElementById("moddevgradle-input");
+    modDevGradleInput.disabled = getMappingSet() === "Yarn";
+    if (modDevGradleInput.disabled && modDevGradleInput.checked) {
+        modDevGradleInput.checked = false;
+        document.getElementById("architectury-loom-input").checked = true;
+    }
 }
 
 function isFabricLikeAvailable() {
@@ -148,6 +231,12 @@ function isFabricLikeAvailable() {
     return fabricInput.checked && quiltInput.checked;
 }
 
+function isForgeLikeAvailable() {
+    const forgeInput = document.getElementById("forge-loader-input");
+    const neoForgeInput = document.getElementById("neoforge-loader-input");
+    return forgeInput.checked && neoForgeInput.checked && isForgeAvailable() && isNeoForgeAvailable();
+}
+
 function isNeoForgeAvailable() {
     const version = mcSelect.value;
     return supports_neoforge(versionsByName[version]);
@@ -160,19 +249,32 @@ function isForgeAvailable() {
 
 function isArchitecturyApiAvailable() {
     const version = mcSelect.value;
-    if (!supports_arch_api(versionsByName[version])) {
-        return false;
-    } else if (document.getElementById("forge-loader-input").checked) {
-        return arch_api_supports_forge(versionsByName[version]);
-    } else {
-        return true;
-    }
+    const subprojects = { forge: document.getElementById("forge-loader-input").checked && isForgeAvailable() };
+    return supports_arch_api(versionsByName[version], subprojects);
 }
 
 function refreshAvailablePlatforms() {
     refreshForgeLikePlatform(isNeoForgeAvailable(), "neoforge");
     refreshForgeLikePlatform(isForgeAvailable(), "forge");
     refreshArchitecturySupport();
+    refreshForgeLikeCheckbox();
+    refreshParchmentSupport();
+}
+
+// Disables Parchment when the version index has no Parchment version for the game version.
+async function refreshParchmentSupport() {
+    const version = mcSelect.value;
+    const available = await supports_parchment(versionsByName[version]);
+    // Ignore the result if another version was selected in the meantime.
+    if (version !== mcSelect.value) return;
+
+    const parchmentInput = document.getElementById("parchment-input");
+    parchmentInput.disabled = !available;
+    if (!available && parchmentInput.checked) {
+        parchmentInput.checked = false;
+        document.getElementById("mojang-mappings-input").checked = true;
+        refreshToolchainSupport();
+    }
 }
 
 function refreshForgeLikePlatform(available, id) {
@@ -204,6 +306,11 @@ function refreshFabricLikeCheckbox() {
     fabricLikeInput.disabled = !hasFabricLike;
 }
 
+function refreshForgeLikeCheckbox() {
+    const forgeLikeInput = document.getElementById("forge-like-input");
+    forgeLikeInput.disabled = !isForgeLikeAvailable();
+}
+
 function isLoaderChecked() {
     return document.getElementById("fabric-loader-input").checked || document.getElementById("forge-loader-input").checked || document.getElementById("neoforge-loader-input").checked || document.getElementById("quilt-loader-input").checked
 }
@@ -217,6 +324,9 @@ document.getElementById("generate-button").onclick = async () => {
     } else if (!isModIdValid()) {
         showError("Mod ID is not valid");
         return;
+    } else if (!isMainClassNameValid()) {
+        showError("Main class name is not valid");
+        return;
     } else if (state.package_name === "") {
         showError("Package name is empty");
         return;
@@ -233,6 +343,8 @@ document.getElementById("generate-button").onclick = async () => {
 modNameInput.value = state.mod_name;
 modIdInput.value = state.mod_id;
 refreshModIdPlaceholder();
+mainClassNameInput.value = state.main_class_name;
+refreshMainClassNamePlaceholder();
 refreshAvailablePlatforms();
 document.getElementById("package-input").value = state.package_name;
 document.getElementById("architectury-api-input").checked = state.dependencies.architectury_api;
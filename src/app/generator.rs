@@ -2,26 +2,126 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::maven::{resolve_latest_version, resolve_matching_version, MavenLibrary};
+use crate::maven::{MavenClient, MavenLibrary, MavenRepository};
 use crate::tap::Tap;
 use crate::templates::*;
-use crate::versions::{LOOM_VERSION, PLUGIN_VERSION, JavaVersion, MinecraftVersionList};
-use crate::versions::index::get_version_index;
-use crate::{MappingSet, ProjectType, Result};
+use crate::versions::{KOTLIN_PLUGIN_VERSION, LOOM_VERSION, PLUGIN_VERSION, JavaVersion, MinecraftVersionList};
+use crate::versions::index::{
+    get_version_index, is_fabric_api_version_for, is_kotlin_for_forge_version_for, is_quilted_fabric_api_version_for,
+    is_yarn_version_for, resolve_pinned_latest_version, resolve_pinned_version, VersionSource,
+    VERSION_INDEX_IS_COMPLETE,
+};
+use crate::filer::FilePermissions;
+use crate::{err, Language, MappingSet, ProjectType, Result, Toolchain};
 use bytes::Bytes;
 use futures::future::join_all;
 use futures::{join, FutureExt};
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
+use strum::IntoEnumIterator;
 
-pub async fn generate(app: &super::GeneratorApp, version_list: &MinecraftVersionList, filer_provider: &impl crate::filer::FilerProvider) -> Result<()> {
+type FilesFuture<'a> = Pin<Box<dyn Future<Output = Result<Vec<FileData>>> + 'a>>;
+type VariableFuture<'a> = Pin<Box<dyn Future<Output = Result<(String, String)>> + 'a>>;
+
+/// The context and template files of a generated project, with all versions resolved.
+struct PreparedProject {
+    context: engine::Context,
+    files: Vec<FileData>,
+    partials: engine::Partials,
+    resolved_versions: Vec<(String, String)>,
+}
+
+/// A description of what [`generate`] would write, produced by [`plan`].
+pub struct GenerationPlan {
+    pub file_name: String,
+    pub files: Vec<PlannedFile>,
+    /// The context variables resolved from Maven, sorted by key.
+    pub resolved_versions: Vec<(String, String)>,
+}
+
+pub struct PlannedFile {
+    pub path: String,
+    pub size: usize,
+    pub permissions: FilePermissions,
+}
+
+pub async fn generate(
+    app: &super::GeneratorApp,
+    version_list: &MinecraftVersionList,
+    version_source: &VersionSource,
+    mode: engine::RenderMode,
+    filer_provider: &impl crate::filer::FilerProvider,
+) -> Result<()> {
+    // Render everything before saving so that nothing is written if a template fails.
+    let rendered = render(app, version_list, version_source, mode).await?;
+
+    filer_provider.use_filer(|filer| {
+        let file_name = compose_file_name(app);
+        filer.set_file_name(file_name);
+
+        for file in rendered {
+            filer.save(file.path.as_str(), &file.content, &file.permissions).tap(|result| {
+                if let Err(err) = result {
+                    eprintln!("Could not save {}: {:?}", file.path, err);
+                }
+            })?;
+        }
+
+        Ok(())
+    })
+    .await
+}
+
+/// Renders the files of a project without saving them, so that they can be post-processed.
+pub async fn render(
+    app: &super::GeneratorApp,
+    version_list: &MinecraftVersionList,
+    version_source: &VersionSource,
+    mode: engine::RenderMode,
+) -> Result<Vec<RenderedFile>> {
+    let PreparedProject { context, files, partials, .. } = prepare(app, version_list, version_source).await?;
+    render_files(&context, files, &partials, mode)
+}
+
+/// Runs the whole generation pipeline without writing anything,
+/// and returns the files that would be written and the resolved versions.
+pub async fn plan(
+    app: &super::GeneratorApp,
+    version_list: &MinecraftVersionList,
+    version_source: &VersionSource,
+    mode: engine::RenderMode,
+) -> Result<GenerationPlan> {
+    let PreparedProject { context, files, partials, mut resolved_versions } = prepare(app, version_list, version_source).await?;
+    let planned_files = render_files(&context, files, &partials, mode)?
+        .into_iter()
+        .map(|file| PlannedFile {
+            path: file.path,
+            size: file.content.len(),
+            permissions: file.permissions,
+        })
+        .collect();
+
+    resolved_versions.sort();
+    Ok(GenerationPlan {
+        file_name: compose_file_name(app),
+        files: planned_files,
+        resolved_versions,
+    })
+}
+
+async fn prepare(
+    app: &super::GeneratorApp,
+    version_list: &MinecraftVersionList,
+    version_source: &VersionSource,
+) -> Result<PreparedProject> {
     let mut context = engine::Context::new();
     // Mod properties
     context.put("PACKAGE_NAME", &app.package_name);
-    context.put("PACKAGE_DIR", &app.package_name.replace(".", "/"));
+    context.put("PACKAGE_DIR", app.package_name.replace(".", "/"));
     let mod_id: String = app.get_effective_mod_id();
     context.put("MOD_ID", mod_id);
+    context.put("MAIN_CLASS_NAME", app.get_effective_main_class_name());
     let escaped_name = escape_json_and_toml(&app.mod_name);
     context.put("MOD_NAME", escaped_name);
 
@@ -31,6 +131,8 @@ pub async fn generate(app: &super::GeneratorApp, version_list: &MinecraftVersion
         .unwrap();
     let java_version = JavaVersion::try_from(game_version.java_version).unwrap();
     context.put("MINECRAFT_VERSION", &game_version.version);
+    // Flag for version-specific templates, e.g. `mc_1_20_4`
+    context.define(format!("mc_{}", game_version.version.replace('.', "_")));
     context.put(
         "GRADLE_JAVA_VERSION",
         java_version.gradle_java_version(),
@@ -72,24 +174,66 @@ pub async fn generate(app: &super::GeneratorApp, version_list: &MinecraftVersion
     context.put("LOOM_VERSION", LOOM_VERSION);
     context.put("PLUGIN_VERSION", PLUGIN_VERSION);
 
+    // Custom Maven repositories
+    let mut mirrors: Vec<String> = Vec::new();
+    for repository in MavenRepository::iter() {
+        let urls = app.repositories.custom_urls(repository);
+        if !urls.is_empty() {
+            let declarations = gradle_maven_repositories(urls);
+            context.put(format!("{}_MAVEN_REPOSITORIES", repository.key().to_uppercase()), &declarations);
+            // The NeoForge repository is only added to NeoForge projects.
+            if repository != MavenRepository::NeoForge {
+                mirrors.push(declarations);
+            }
+        }
+    }
+    if !mirrors.is_empty() {
+        context.put("MAVEN_MIRRORS", mirrors.join("; "));
+    }
+
     // Setup version resolving
-    let client = Arc::new(reqwest::ClientBuilder::new().build()?);
-    let versions = get_version_index(client.clone(), &game_version).await?;
-    let mut files: Vec<Pin<Box<dyn Future<Output = Result<Vec<FileData>>>>>> =
-        vec![Box::pin(shared::shared_files(client.clone()))];
-    let mut variables: Vec<Pin<Box<dyn Future<Output = Result<(String, String)>>>>> = Vec::new();
+    let maven = match version_source {
+        VersionSource::Online(maven) => maven.clone(),
+        VersionSource::Offline(_) => MavenClient::new(offline_http_client()?),
+    };
+    let maven = maven.with_repositories(app.repositories.clone());
+    let pins = &app.pins;
+    let (versions, complete) = match version_source {
+        VersionSource::Online(_) => (get_version_index(&maven, game_version, pins).await?, VERSION_INDEX_IS_COMPLETE),
+        VersionSource::Offline(index) => {
+            let mut versions = index.get(game_version)?.clone();
+            versions.apply_pins(pins)?;
+            (versions, true)
+        }
+    };
+    let client = Arc::new(maven.http().clone());
+    // The versions that must be taken from the version index instead of Maven,
+    // so that they match what e.g. `supports_parchment` on the web reports.
+    let indexed = complete.then_some(&versions);
+    let mut files: Vec<FilesFuture<'_>> = vec![Box::pin(shared::all_files(client.clone()))];
+    let mut variables: Vec<VariableFuture<'_>> = Vec::new();
 
     // Mappings
     match app.mapping_set {
         MappingSet::Mojang => context.define("mojang_mappings"),
+        MappingSet::MojangParchment => {
+            context.define("mojang_mappings");
+            context.define("parchment");
+            variables.push(lookup_version(
+                "PARCHMENT_VERSION",
+                indexed.map(|v| v.parchment.clone()),
+                resolve_pinned_latest_version(&maven, MavenLibrary::parchment(game_version), pins.parchment.as_ref()),
+            ));
+        }
         MappingSet::Yarn => {
             context.define("yarn");
-            variables.push(Box::pin(add_key(
+            variables.push(lookup_version(
                 "YARN_MAPPINGS",
-                resolve_matching_version(&client, MavenLibrary::yarn(), |version| {
-                    version.starts_with(&format!("{}+", game_version.version))
+                indexed.map(|v| v.yarn.clone()),
+                resolve_pinned_version(&maven, MavenLibrary::yarn(), pins.yarn.as_ref(), |version| {
+                    is_yarn_version_for(game_version, version)
                 }),
-            )));
+            ));
         }
     }
 
@@ -98,20 +242,22 @@ pub async fn generate(app: &super::GeneratorApp, version_list: &MinecraftVersion
         ProjectType::Multiplatform => {
             let mut platforms: Vec<&'static str> = vec![];
             files.push(Box::pin(multiplatform::all_files(client.clone())));
-            variables.push(Box::pin(add_key(
+            variables.push(lookup_version(
                 "FABRIC_LOADER_VERSION",
-                resolve_latest_version(&client, MavenLibrary::fabric_loader()),
-            )));
+                indexed.map(|v| v.fabric_loader.clone()),
+                resolve_pinned_latest_version(&maven, MavenLibrary::fabric_loader(), pins.fabric_loader.as_ref()),
+            ));
 
             if app.subprojects.fabric {
                 context.define("fabric");
                 files.push(Box::pin(fabric::all_files(client.clone())));
-                variables.push(Box::pin(add_key(
+                variables.push(lookup_version(
                     "FABRIC_API_VERSION",
-                    resolve_matching_version(&client, MavenLibrary::fabric_api(), |version| {
-                        version.ends_with(&format!("+{}", game_version.fabric.fabric_api_branch.as_ref().unwrap_or(&game_version.version)))
+                    indexed.map(|v| v.fabric_api.clone()),
+                    resolve_pinned_version(&maven, MavenLibrary::fabric_api(), pins.fabric_api.as_ref(), |version| {
+                        is_fabric_api_version_for(game_version, version)
                     }),
-                )));
+                ));
                 platforms.push("fabric");
             }
 
@@ -123,48 +269,41 @@ pub async fn generate(app: &super::GeneratorApp, version_list: &MinecraftVersion
             if app.subprojects.forge {
                 context.define("forge");
                 files.push(Box::pin(forge::all_files(client.clone())));
-                if let Some(version) = versions.forge {
-                    variables.push(Box::pin(add_key(
-                        "FORGE_VERSION",
-                        std::future::ready(Ok(version)),
-                    )));
-                }
+                variables.push(required_version("FORGE_VERSION", versions.forge.clone()));
                 platforms.push("forge");
             }
 
+            if app.subprojects.forge_likes {
+                context.define("forge_like");
+                files.push(Box::pin(forge_like::all_files(client.clone())));
+            }
+
             if app.subprojects.neoforge {
                 context.define("neoforge");
-                files.push(Box::pin(neoforge::main_files(client.clone())));
-                if let Some(version) = versions.neoforge {
-                    variables.push(Box::pin(add_key(
-                        "NEOFORGE_VERSION",
-                        std::future::ready(Ok(version)),
-                    )));
-                }
-                if game_version.version == "1.20.4" {
-                    context.put("NEOFORGE_METADATA_FILE_NAME", "mods.toml");
-                    files.push(Box::pin(neoforge::mods_toml_files(client.clone())));
-                } else {
-                    context.put("NEOFORGE_METADATA_FILE_NAME", "neoforge.mods.toml");
-                    files.push(Box::pin(neoforge::neoforge_mods_toml_files(client.clone())));
+                files.push(Box::pin(neoforge::all_files(client.clone())));
+                variables.push(required_version("NEOFORGE_VERSION", versions.neoforge.clone()));
+                let yarn_patch = game_version.neoforge.as_ref().and_then(|neoforge| neoforge.yarn_patch_version.as_ref());
+                if app.mapping_set == MappingSet::Yarn && yarn_patch.is_some() {
+                    variables.push(required_version("NEOFORGE_YARN_PATCH_VERSION", versions.neoforge_yarn_patch.clone()));
                 }
-                context.maybe_put("NEOFORGE_YARN_PATCH_VERSION", versions.neoforge_yarn_patch);
                 platforms.push("neoforge");
             }
 
             if app.subprojects.quilt {
                 context.define("quilt");
                 files.push(Box::pin(quilt::all_files(client.clone())));
-                variables.push(Box::pin(add_key(
+                variables.push(lookup_version(
                     "QUILT_LOADER_VERSION",
-                    resolve_latest_version(&client, MavenLibrary::quilt_loader())
-                )));
-                variables.push(Box::pin(add_key(
+                    indexed.map(|v| v.quilt_loader.clone()),
+                    resolve_pinned_latest_version(&maven, MavenLibrary::quilt_loader(), pins.quilt_loader.as_ref()),
+                ));
+                variables.push(lookup_version(
                     "QUILTED_FABRIC_API_VERSION",
-                    resolve_matching_version(&client, MavenLibrary::quilted_fabric_api(), |version| {
-                        version.ends_with(&format!("-{}", game_version.version))
+                    indexed.map(|v| v.quilted_fabric_api.clone()),
+                    resolve_pinned_version(&maven, MavenLibrary::quilted_fabric_api(), pins.quilted_fabric_api.as_ref(), |version| {
+                        is_quilted_fabric_api_version_for(game_version, version)
                     }),
-                )));
+                ));
                 platforms.push("quilt");
             }
 
@@ -173,83 +312,220 @@ pub async fn generate(app: &super::GeneratorApp, version_list: &MinecraftVersion
 
             if app.dependencies.architectury_api {
                 context.define("architectury_api");
-                if let Some(version) = versions.architectury_api {
-                    variables.push(Box::pin(add_key(
-                        "ARCHITECTURY_API_VERSION",
-                        std::future::ready(Ok(version)),
-                    )));
-                }
+                variables.push(required_version("ARCHITECTURY_API_VERSION", versions.architectury_api.clone()));
             }
         }
         ProjectType::NeoForge => {
-            files.push(Box::pin(neoforge_only::main_files(client.clone())));
-            if let Some(version) = versions.neoforge {
-                variables.push(Box::pin(add_key(
-                    "NEOFORGE_VERSION",
-                    std::future::ready(Ok(version)),
-                )));
+            files.push(Box::pin(neoforge_only::all_files(client.clone())));
+            variables.push(required_version("NEOFORGE_VERSION", versions.neoforge.clone()));
+            let yarn_patch = game_version.neoforge.as_ref().and_then(|neoforge| neoforge.yarn_patch_version.as_ref());
+            if app.mapping_set == MappingSet::Yarn && yarn_patch.is_some() {
+                variables.push(required_version("NEOFORGE_YARN_PATCH_VERSION", versions.neoforge_yarn_patch.clone()));
             }
-            if game_version.version == "1.20.4" {
-                context.put("NEOFORGE_METADATA_FILE_NAME", "mods.toml");
-                files.push(Box::pin(neoforge_only::mods_toml_files(client.clone())));
-            } else {
-                context.put("NEOFORGE_METADATA_FILE_NAME", "neoforge.mods.toml");
-                files.push(Box::pin(neoforge_only::neoforge_mods_toml_files(client.clone())));
+            if app.toolchain == Toolchain::ModDevGradle {
+                if !app.toolchain.supports(app.mapping_set) {
+                    return Err(err!("{} can't be used with {}", app.mapping_set.name(), app.toolchain.name()));
+                }
+                context.define("moddevgradle");
+                files.push(Box::pin(neoforge_moddev::all_files(client.clone())));
+                variables.push(lookup_version(
+                    "MODDEVGRADLE_VERSION",
+                    indexed.map(|v| v.moddevgradle.clone()),
+                    resolve_pinned_latest_version(&maven, MavenLibrary::moddevgradle(), pins.moddevgradle.as_ref()),
+                ));
             }
-            context.maybe_put("NEOFORGE_YARN_PATCH_VERSION", versions.neoforge_yarn_patch);
         }
         ProjectType::Forge => {
             files.push(Box::pin(forge_only::all_files(client.clone())));
-            if let Some(version) = versions.forge {
-                variables.push(Box::pin(add_key(
-                    "FORGE_VERSION",
-                    std::future::ready(Ok(version)),
-                )));
-            }
+            variables.push(required_version("FORGE_VERSION", versions.forge.clone()));
+        }
+        ProjectType::Fabric => {
+            files.push(Box::pin(fabric_only::all_files(client.clone())));
+            variables.push(lookup_version(
+                "FABRIC_LOADER_VERSION",
+                indexed.map(|v| v.fabric_loader.clone()),
+                resolve_pinned_latest_version(&maven, MavenLibrary::fabric_loader(), pins.fabric_loader.as_ref()),
+            ));
+            variables.push(lookup_version(
+                "FABRIC_API_VERSION",
+                indexed.map(|v| v.fabric_api.clone()),
+                resolve_pinned_version(&maven, MavenLibrary::fabric_api(), pins.fabric_api.as_ref(), |version| {
+                    is_fabric_api_version_for(game_version, version)
+                }),
+            ));
+        }
+        ProjectType::Quilt => {
+            files.push(Box::pin(quilt_only::all_files(client.clone())));
+            variables.push(lookup_version(
+                "QUILT_LOADER_VERSION",
+                indexed.map(|v| v.quilt_loader.clone()),
+                resolve_pinned_latest_version(&maven, MavenLibrary::quilt_loader(), pins.quilt_loader.as_ref()),
+            ));
+            variables.push(lookup_version(
+                "QUILTED_FABRIC_API_VERSION",
+                indexed.map(|v| v.quilted_fabric_api.clone()),
+                resolve_pinned_version(&maven, MavenLibrary::quilted_fabric_api(), pins.quilted_fabric_api.as_ref(), |version| {
+                    is_quilted_fabric_api_version_for(game_version, version)
+                }),
+            ));
+        }
+    }
+
+    // Language adapters
+    if app.language == Language::Kotlin {
+        context.define("kotlin");
+        context.put("KOTLIN_PLUGIN_VERSION", KOTLIN_PLUGIN_VERSION);
+        let (fabric, forge, neoforge) = match app.project_type {
+            ProjectType::Multiplatform => (
+                app.subprojects.fabric || app.subprojects.quilt,
+                app.subprojects.forge,
+                app.subprojects.neoforge,
+            ),
+            ProjectType::NeoForge => (false, false, true),
+            ProjectType::Forge => (false, true, false),
+            ProjectType::Fabric | ProjectType::Quilt => (true, false, false),
+        };
+
+        // Quilt loads Fabric Language Kotlin as well.
+        if fabric {
+            variables.push(lookup_version(
+                "FABRIC_LANGUAGE_KOTLIN_VERSION",
+                indexed.map(|v| v.fabric_language_kotlin.clone()),
+                resolve_pinned_latest_version(&maven, MavenLibrary::fabric_language_kotlin(), pins.fabric_language_kotlin.as_ref()),
+            ));
+        }
+
+        if forge || neoforge {
+            context.define("kotlin_for_forge");
+        }
+
+        if forge {
+            let major = game_version.forge.as_ref()
+                .and_then(|forge| forge.kotlin_for_forge_version.as_ref())
+                .ok_or_else(|| err!("Kotlin for Forge is not available for Minecraft {}", game_version.version))?;
+            context.put("KOTLIN_FOR_FORGE_MAJOR", major);
+            variables.push(lookup_version(
+                "KOTLIN_FOR_FORGE_VERSION",
+                indexed.map(|v| v.kotlin_for_forge.clone()),
+                resolve_pinned_version(
+                    &maven,
+                    MavenLibrary::kotlin_for_forge(),
+                    pins.kotlin_for_forge.as_ref(),
+                    |version| is_kotlin_for_forge_version_for(major, version),
+                ),
+            ));
+        }
+
+        if neoforge {
+            let major = game_version.neoforge.as_ref()
+                .and_then(|neoforge| neoforge.kotlin_for_forge_version.as_ref())
+                .ok_or_else(|| err!("Kotlin for Forge is not available for NeoForge on Minecraft {}", game_version.version))?;
+            context.put("NEOFORGE_KOTLIN_FOR_FORGE_MAJOR", major);
+            variables.push(lookup_version(
+                "NEOFORGE_KOTLIN_FOR_FORGE_VERSION",
+                indexed.map(|v| v.kotlin_for_forge_neoforge.clone()),
+                resolve_pinned_version(
+                    &maven,
+                    MavenLibrary::kotlin_for_forge_neoforge(),
+                    pins.kotlin_for_forge_neoforge.as_ref(),
+                    |version| is_kotlin_for_forge_version_for(major, version),
+                ),
+            ));
         }
     }
 
     // Resolve versions
-    let (files, variables) = join!(join_all(files), join_all(variables));
+    let (files, variables, partials) = join!(join_all(files), join_all(variables), partials::load_partials(client));
     let files: Vec<FileData> = files
         .into_iter()
         .collect::<Result<Vec<Vec<FileData>>>>()?
         .into_iter()
         .flatten()
         .collect();
+    let mut resolved_versions = Vec::with_capacity(variables.len());
     for result in variables {
         let (key, value) = result?;
-        context.put(key, value);
+        context.put(&key, &value);
+        resolved_versions.push((key, value));
     }
 
-    filer_provider.use_filer(|filer| {
-        let file_name = compose_file_name(app);
-        filer.set_file_name(file_name);
+    Ok(PreparedProject { context, files, partials: partials?, resolved_versions })
+}
 
-        for file_data in files {
-            let path = engine::apply_variables(&context, file_data.path.as_str(), false);
-            let content: Bytes = match &file_data.content {
-                FileContent::Binary(bytes) => bytes.clone(),
-                FileContent::Text(text) => {
-                    let applied: String =
-                        engine::apply_template(&context, engine::read_template(text).unwrap())
-                            .iter()
-                            .map(|line| line.to_owned() + "\n")
-                            .collect();
-                    Bytes::from(applied)
-                }
-            };
+/// Creates the HTTP client used in offline mode, where it only downloads templates on the web.
+/// Natively, one client is shared because creating one loads the system's TLS certificates.
+fn offline_http_client() -> Result<reqwest::Client> {
+    #[cfg(not(target_family = "wasm"))]
+    {
+        static CLIENT: std::sync::OnceLock<reqwest::Client> = std::sync::OnceLock::new();
+        if let Some(client) = CLIENT.get() {
+            return Ok(client.clone());
+        }
+        let client = reqwest::ClientBuilder::new().build()?;
+        Ok(CLIENT.get_or_init(|| client).clone())
+    }
 
-            filer.save(path.as_str(), &content, &file_data.permissions).tap(|result| {
-                if let Err(err) = result {
-                    eprintln!("Could not save {}: {:?}", path, err);
-                }
-            })?;
+    #[cfg(target_family = "wasm")]
+    Ok(reqwest::ClientBuilder::new().build()?)
+}
+
+/// A generated file with its path in the project.
+#[derive(Clone)]
+pub struct RenderedFile {
+    pub path: String,
+    pub content: Bytes,
+    pub permissions: FilePermissions,
+}
+
+/// Renders the output paths and contents of the template files.
+/// In strict mode, the unresolved placeholders of all files are reported together.
+fn render_files(
+    context: &engine::Context,
+    files: Vec<FileData>,
+    partials: &engine::Partials,
+    mode: engine::RenderMode,
+) -> Result<Vec<RenderedFile>> {
+    let mut rendered = Vec::with_capacity(files.len());
+    let mut unresolved = Vec::new();
+
+    for file_data in files {
+        if let FileContent::Text(text) = &file_data.content {
+            let front_matter = engine::read_front_matter(text)
+                .map_err(|err| err!("Could not read front matter of template {}: {}", file_data.path, err))?;
+            if !front_matter.is_included(context) {
+                continue;
+            }
         }
 
-        Ok(())
-    })
-    .await
+        let path = engine::apply_path_variables(context, &file_data.path)
+            .map_err(|err| err!("Could not render path of template {}: {}", file_data.path, err))?;
+        let content: Bytes = match &file_data.content {
+            FileContent::Binary(bytes) => bytes.clone(),
+            FileContent::Text(text) => {
+                let template = engine::read_template_with_partials(text, partials)
+                    .map_err(|err| err!("Could not read template {}: {}", file_data.path, err))?;
+                match engine::apply_template_with_mode(context, template, mode, &file_data.path) {
+                    Ok(lines) => Bytes::from(lines.iter().map(|line| line.to_owned() + "\n").collect::<String>()),
+                    Err(engine::UnresolvedPlaceholders(placeholders)) => {
+                        unresolved.extend(placeholders);
+                        Bytes::new()
+                    }
+                }
+            }
+        };
+
+        rendered.push(RenderedFile {
+            path,
+            content,
+            permissions: file_data.permissions,
+        });
+    }
+
+    if !unresolved.is_empty() {
+        return Err(engine::UnresolvedPlaceholders(unresolved).into());
+    }
+
+    Ok(rendered)
 }
 
 pub fn compose_file_name(app: &super::GeneratorApp) -> String {
@@ -271,22 +547,52 @@ pub fn compose_file_name(app: &super::GeneratorApp) -> String {
                 }
             }
 
-            if app.subprojects.neoforge {
-                file_name += "-neoforge";
-            }
+            if app.subprojects.forge && app.subprojects.neoforge && app.subprojects.forge_likes {
+                file_name += "-forge-like";
+            } else {
+                if app.subprojects.neoforge {
+                    file_name += "-neoforge";
+                }
 
-            if app.subprojects.forge {
-                file_name += "-forge";
+                if app.subprojects.forge {
+                    file_name += "-forge";
+                }
             }
         },
-        ProjectType::NeoForge => file_name += "-neoforge-only",
+        ProjectType::NeoForge => match app.toolchain {
+            Toolchain::ArchitecturyLoom => file_name += "-neoforge-only",
+            Toolchain::ModDevGradle => file_name += "-neoforge-moddev",
+        },
         ProjectType::Forge => file_name += "-forge-only",
+        ProjectType::Fabric => file_name += "-fabric-only",
+        ProjectType::Quilt => file_name += "-quilt-only",
     }
 
     file_name += "-template";
     file_name
 }
 
+/// Takes a version that is always resolved with the other versions of the game version,
+/// failing if the version index doesn't contain it.
+fn required_version<'a>(key: &'static str, version: Option<String>) -> VariableFuture<'a> {
+    let version = version.ok_or_else(|| err!("The version index does not contain {}", key));
+    Box::pin(add_key(key, std::future::ready(version)))
+}
+
+/// Takes a version from the complete version index if present, or resolves it online otherwise.
+fn lookup_version<'a, F>(key: &'static str, indexed: Option<Option<String>>, online: F) -> VariableFuture<'a>
+where
+    F: Future<Output = Result<String>> + 'a,
+{
+    match indexed {
+        Some(version) => {
+            let version = version.ok_or_else(|| err!("The version index does not contain {}", key));
+            Box::pin(add_key(key, std::future::ready(version)))
+        }
+        None => Box::pin(add_key(key, online)),
+    }
+}
+
 fn add_key<F>(key: &'static str, future: F) -> impl Future<Output = Result<(String, String)>>
 where
     F: Future<Output = Result<String>>,
@@ -294,6 +600,14 @@ where
     future.map(|result| result.map(|version| (key.to_owned(), version)))
 }
 
+/// Composes Gradle `maven` repository declarations for the URLs on a single line.
+fn gradle_maven_repositories(urls: &[String]) -> String {
+    urls.iter()
+        .map(|url| format!("maven {{ url = '{}' }}", url))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
 /// Escapes a raw string so it can be embedded in a JSON or TOML quoted string value.
 fn escape_json_and_toml(input: &str) -> String {
     let mut output = String::with_capacity(input.len());
@@ -316,6 +630,130 @@ fn escape_json_and_toml(input: &str) -> String {
 
 #[cfg(test)]
 mod tests {
+    use crate::templates::engine::RenderMode;
+    use crate::versions::index::{VersionIndex, VersionSource};
+    use crate::versions::pins::VersionConstraint;
+    use crate::versions::MinecraftVersionList;
+    use crate::{GeneratorApp, MappingSet, ProjectType, Subprojects, Toolchain};
+
+    fn load_version_list() -> MinecraftVersionList {
+        serde_json::from_str(include_str!("../minecraft_versions.json")).unwrap()
+    }
+
+    fn offline_source(json: &str) -> VersionSource {
+        let index: VersionIndex = serde_json::from_str(json).unwrap();
+        VersionSource::Offline(index)
+    }
+
+    fn fabric_app(version_list: &MinecraftVersionList) -> GeneratorApp {
+        GeneratorApp {
+            mod_name: "My Mod".to_owned(),
+            game_version: "1.21.1".to_owned(),
+            subprojects: Subprojects { fabric: true, ..Default::default() },
+            mapping_set: MappingSet::Yarn,
+            ..GeneratorApp::new(version_list)
+        }
+    }
+
+    #[tokio::test]
+    async fn offline_plan_uses_version_index() {
+        let version_list = load_version_list();
+        let source = offline_source(r#"{
+            "1.21.1": {
+                "architectury_api": "13.0.0",
+                "forge": null,
+                "neoforge": "21.1.0",
+                "fabric_loader": "0.16.0",
+                "fabric_api": "0.100.0+1.21.1",
+                "yarn": "1.21.1+build.1"
+            }
+        }"#);
+
+        let plan = super::plan(&fabric_app(&version_list), &version_list, &source, RenderMode::Strict).await.unwrap();
+        assert!(plan.resolved_versions.contains(&("YARN_MAPPINGS".to_owned(), "1.21.1+build.1".to_owned())));
+        assert!(plan.files.iter().any(|file| file.path == "fabric/src/main/resources/fabric.mod.json"));
+    }
+
+    #[tokio::test]
+    async fn offline_plan_fails_on_missing_version() {
+        let version_list = load_version_list();
+        let source = offline_source(r#"{
+            "1.21.1": { "architectury_api": "13.0.0", "forge": null, "neoforge": "21.1.0" }
+        }"#);
+
+        let result = super::plan(&fabric_app(&version_list), &version_list, &source, RenderMode::Strict).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn offline_plan_applies_pins() {
+        let version_list = load_version_list();
+        let index = r#"{
+            "1.21.1": {
+                "architectury_api": "13.0.0",
+                "forge": null,
+                "neoforge": "21.1.0",
+                "fabric_loader": "0.16.10",
+                "fabric_api": "0.100.0+1.21.1",
+                "yarn": "1.21.1+build.1"
+            }
+        }"#;
+
+        let mut app = fabric_app(&version_list);
+        app.pins.set("fabric-loader", VersionConstraint::parse("0.16.9").unwrap()).unwrap();
+        app.pins.set("yarn", VersionConstraint::parse("1.21.1+build.*").unwrap()).unwrap();
+        let plan = super::plan(&app, &version_list, &offline_source(index), RenderMode::Strict).await.unwrap();
+        assert!(plan.resolved_versions.contains(&("FABRIC_LOADER_VERSION".to_owned(), "0.16.9".to_owned())));
+        assert!(plan.resolved_versions.contains(&("YARN_MAPPINGS".to_owned(), "1.21.1+build.1".to_owned())));
+
+        app.pins.set("fabric_api", VersionConstraint::parse(">=0.101").unwrap()).unwrap();
+        let result = super::plan(&app, &version_list, &offline_source(index), RenderMode::Strict).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn offline_plan_takes_moddevgradle_from_index() {
+        let version_list = load_version_list();
+        let source = offline_source(r#"{
+            "1.21.1": { "architectury_api": "13.0.0", "forge": null, "neoforge": "21.1.0", "moddevgradle": "2.0.0" }
+        }"#);
+        let mut app = GeneratorApp {
+            mod_name: "My Mod".to_owned(),
+            game_version: "1.21.1".to_owned(),
+            project_type: ProjectType::NeoForge,
+            toolchain: Toolchain::ModDevGradle,
+            mapping_set: MappingSet::Mojang,
+            ..GeneratorApp::new(&version_list)
+        };
+
+        let plan = super::plan(&app, &version_list, &source, RenderMode::Strict).await.unwrap();
+        assert!(plan.resolved_versions.contains(&("MODDEVGRADLE_VERSION".to_owned(), "2.0.0".to_owned())));
+
+        app.pins.set("moddevgradle", VersionConstraint::parse("1.0.21").unwrap()).unwrap();
+        let plan = super::plan(&app, &version_list, &source, RenderMode::Strict).await.unwrap();
+        assert!(plan.resolved_versions.contains(&("MODDEVGRADLE_VERSION".to_owned(), "1.0.21".to_owned())));
+    }
+
+    #[tokio::test]
+    async fn offline_plan_reports_missing_versions() {
+        let version_list = load_version_list();
+        let source = offline_source(r#"{
+            "1.20.1": { "architectury_api": "9.2.14", "forge": null, "neoforge": null }
+        }"#);
+        let app = GeneratorApp {
+            mod_name: "My Mod".to_owned(),
+            game_version: "1.20.1".to_owned(),
+            project_type: ProjectType::Forge,
+            mapping_set: MappingSet::Mojang,
+            ..GeneratorApp::new(&version_list)
+        };
+
+        for mode in [RenderMode::Strict, RenderMode::Lenient] {
+            let error = super::plan(&app, &version_list, &source, mode).await.err().unwrap();
+            assert_eq!(error.to_string(), "The version index does not contain FORGE_VERSION");
+        }
+    }
+
     #[test]
     fn nothing_needs_escaping() {
         let input = "Hello, world‽ 🧶";
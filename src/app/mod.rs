@@ -4,11 +4,13 @@
 
 use serde::{Deserialize, Serialize};
 use strum::EnumIter;
-use crate::versions::MinecraftVersionList;
+use crate::maven::MavenRepositoryUrls;
+use crate::versions::pins::VersionPins;
+use crate::versions::{MinecraftVersion, MinecraftVersionList};
 
 pub mod generator;
 
-pub const SUBHEADING_STYLE: &'static str = "subheading";
+pub const SUBHEADING_STYLE: &str = "subheading";
 
 #[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ProjectType {
@@ -16,12 +18,15 @@ pub enum ProjectType {
     Multiplatform,
     NeoForge,
     Forge,
+    Fabric,
+    Quilt,
 }
 
 #[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, EnumIter)]
 pub enum MappingSet {
     #[default]
     Mojang,
+    MojangParchment,
     Yarn,
 }
 
@@ -29,6 +34,7 @@ impl MappingSet {
     pub fn name(&self) -> &'static str {
         match self {
             Self::Mojang => "Official Mojang mappings",
+            Self::MojangParchment => "Official Mojang mappings with Parchment",
             Self::Yarn => "Yarn",
         }
     }
@@ -36,21 +42,95 @@ impl MappingSet {
     pub fn description(&self) -> &'static str {
         match self {
             Self::Mojang => "The official obfuscation maps published by Mojang.",
+            Self::MojangParchment => "Mojang's mappings with parameter names and javadoc from ParchmentMC.",
             Self::Yarn => "A libre mapping set maintained by FabricMC.",
         }
     }
 }
 
-#[derive(Default, Serialize, Deserialize)]
+/// The programming language of the generated code.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, EnumIter)]
+pub enum Language {
+    #[default]
+    Java,
+    Kotlin,
+}
+
+impl Language {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Java => "Java",
+            Self::Kotlin => "Kotlin",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Java => "",
+            Self::Kotlin => "Uses Fabric Language Kotlin and Kotlin for Forge as language adapters.",
+        }
+    }
+}
+
+/// The Gradle plugin that builds NeoForge-only projects.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, EnumIter)]
+pub enum Toolchain {
+    #[default]
+    ArchitecturyLoom,
+    ModDevGradle,
+}
+
+impl Toolchain {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ArchitecturyLoom => "Architectury Loom",
+            Self::ModDevGradle => "ModDevGradle",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::ArchitecturyLoom => "The same Loom setup as multiplatform projects.",
+            Self::ModDevGradle => "NeoForged's Gradle plugin, like the official MDK.",
+        }
+    }
+
+    /// ModDevGradle only supports Mojang's mappings.
+    pub fn supports(&self, mapping_set: MappingSet) -> bool {
+        *self != Self::ModDevGradle || mapping_set != MappingSet::Yarn
+    }
+}
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Subprojects {
     pub fabric: bool,
     pub forge: bool,
     pub neoforge: bool,
     pub quilt: bool,
     pub fabric_likes: bool,
+    pub forge_likes: bool,
+}
+
+impl Subprojects {
+    /// Checks whether these subprojects can be created together on a game version.
+    pub fn is_available_on(&self, game_version: &MinecraftVersion) -> bool {
+        (!self.forge || game_version.forge.is_some())
+            && (!self.neoforge || game_version.neoforge.is_some())
+            && (!self.fabric_likes || (self.fabric && self.quilt))
+            && (!self.forge_likes || (self.forge && self.neoforge))
+    }
+
+    /// Checks whether Architectury API can be used with these subprojects on a game version.
+    /// Architectury API doesn't support Forge since Minecraft 1.20.5 (Forge 50).
+    pub fn supports_architectury_api(&self, game_version: &MinecraftVersion) -> bool {
+        game_version.architectury.api_version.is_some()
+            && (!self.forge || game_version.forge.as_ref().is_some_and(|forge| forge.major_version < 50))
+    }
 }
 
 #[derive(Serialize, Deserialize)]
+#[serde(default)]
 pub struct Dependencies {
     pub architectury_api: bool,
 }
@@ -67,12 +147,24 @@ impl Default for Dependencies {
 pub struct GeneratorApp {
     pub mod_name: String,
     pub mod_id: String,
+    /// The name of the main class. If empty, it's derived from the mod name.
+    #[serde(default)]
+    pub main_class_name: String,
     pub package_name: String,
     pub game_version: String,
     pub project_type: ProjectType,
     pub subprojects: Subprojects,
     pub mapping_set: MappingSet,
+    /// The build toolchain of NeoForge-only projects.
+    #[serde(default)]
+    pub toolchain: Toolchain,
+    #[serde(default)]
+    pub language: Language,
     pub dependencies: Dependencies,
+    #[serde(default, skip_serializing_if = "MavenRepositoryUrls::is_empty")]
+    pub repositories: MavenRepositoryUrls,
+    #[serde(default, skip_serializing_if = "VersionPins::is_empty")]
+    pub pins: VersionPins,
 }
 
 impl GeneratorApp {
@@ -80,12 +172,17 @@ impl GeneratorApp {
         Self {
             mod_name: "Example Mod".to_owned(),
             mod_id: String::new(),
+            main_class_name: String::new(),
             package_name: "com.example".to_owned(),
             game_version: list.latest_version.clone(),
             project_type: Default::default(),
             subprojects: Default::default(),
             mapping_set: Default::default(),
+            toolchain: Default::default(),
+            language: Default::default(),
             dependencies: Default::default(),
+            repositories: Default::default(),
+            pins: Default::default(),
         }
     }
 
@@ -96,4 +193,12 @@ impl GeneratorApp {
             self.mod_id.clone()
         }
     }
+
+    pub fn get_effective_main_class_name(&self) -> String {
+        if self.main_class_name.is_empty() {
+            crate::class_names::to_class_name(&self.mod_name)
+        } else {
+            self.main_class_name.clone()
+        }
+    }
 }
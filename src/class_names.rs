@@ -0,0 +1,92 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::{err, Result};
+
+const JAVA_KEYWORDS: [&str; 53] = [
+    "abstract", "assert", "boolean", "break", "byte", "case", "catch", "char", "class", "const",
+    "continue", "default", "do", "double", "else", "enum", "extends", "final", "finally", "float",
+    "for", "goto", "if", "implements", "import", "instanceof", "int", "interface", "long", "native",
+    "new", "package", "private", "protected", "public", "return", "short", "static", "strictfp", "super",
+    "switch", "synchronized", "this", "throw", "throws", "transient", "try", "void", "volatile", "while",
+    "true", "false", "null",
+];
+
+/// Types imported by the generated main classes, which a main class can't share a name with.
+const IMPORTED_TYPES: [&str; 5] = [
+    "Mod", "ModContainer", "ModInitializer", "ClientModInitializer", "FMLJavaModLoadingContext",
+];
+
+fn is_valid_class_name_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_' || c == '$'
+}
+
+fn is_valid_in_class_name(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '$'
+}
+
+pub fn validate_class_name<S: AsRef<str>>(name: S) -> Result<()> {
+    let name = name.as_ref();
+    let mut chars = name.chars();
+    let Some(head) = chars.next() else {
+        return Err(err!("Class name is empty"));
+    };
+
+    if !is_valid_class_name_start(head) {
+        return Err(err!("'{}' is not valid at the start of a class name", head));
+    }
+
+    for c in chars {
+        if !is_valid_in_class_name(c) {
+            return Err(err!("'{}' is not valid in class names", c));
+        }
+    }
+
+    if JAVA_KEYWORDS.contains(&name) {
+        return Err(err!("'{}' is a reserved Java keyword", name));
+    }
+
+    if IMPORTED_TYPES.contains(&name) {
+        return Err(err!("'{}' clashes with a type imported by the main class", name));
+    }
+
+    Ok(())
+}
+
+pub fn is_valid_class_name<S: AsRef<str>>(name: S) -> bool {
+    validate_class_name(name).is_ok()
+}
+
+/// Converts a mod name to a class name in PascalCase, e.g. `My Cool Mod` to `MyCoolMod`.
+/// Names that clash with a type imported by the main class get a `Main` suffix.
+pub fn to_class_name<S: AsRef<str>>(name: S) -> String {
+    let pascal = crate::templates::engine::filters::apply_filter("pascal", name.as_ref()).unwrap_or_default();
+    let class_name: String = pascal
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .skip_while(|c| !is_valid_class_name_start(*c))
+        .collect();
+
+    if IMPORTED_TYPES.contains(&class_name.as_str()) {
+        format!("{class_name}Main")
+    } else {
+        class_name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn class_names_from_mod_names() {
+        assert_eq!(to_class_name("My Cool Mod"), "MyCoolMod");
+        assert_eq!(to_class_name("3D café-tools"), "DCafTools");
+        assert!(is_valid_class_name(to_class_name("Hello, world‽ 🧶")));
+        assert!(!is_valid_class_name("2Fast"));
+        assert!(!is_valid_class_name("class"));
+        assert!(!is_valid_class_name("Mod"));
+        assert_eq!(to_class_name("mod"), "ModMain");
+    }
+}
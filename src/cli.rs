@@ -2,15 +2,23 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use clap::Parser;
+use clap::builder::PossibleValue;
+use clap::{Parser, ValueEnum};
 use cliclack::{confirm, input, intro, multiselect, outro, select, spinner};
 use strum::IntoEnumIterator;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use crate::{err, Dependencies, GeneratorApp, MappingSet, ProjectType, Result, Subprojects};
+use crate::{err, Dependencies, GeneratorApp, Language, MappingSet, ProjectType, Result, Subprojects, Toolchain};
+use crate::config::{read_config_file, write_config_file, PartialGeneratorApp};
+use crate::maven::cache::{CachePolicy, MetadataCache};
+use crate::maven::{MavenClient, MavenRepositoryUrls};
 use crate::filer::{FilerProvider, ZipFilerProvider};
 use crate::filer::native::{DirectoryFilerProvider, FsZipWriteTarget};
 use crate::result::ResultContext;
+use crate::templates::engine::RenderMode;
+use crate::versions::index::{VersionIndex, VersionSource};
+use crate::versions::pins::{VersionConstraint, VersionPins};
 use crate::versions::{MinecraftVersion, MinecraftVersionList};
 
 static MINECRAFT_VERSIONS_JSON: &str = include_str!("minecraft_versions.json");
@@ -23,11 +31,238 @@ struct Args {
     /// Output a zip instead of a directory
     #[arg(short, long)]
     zip: bool,
+    /// Print the files and resolved versions that would be generated without writing anything
+    #[arg(long)]
+    dry_run: bool,
+    /// Take all dependency versions from a version index file instead of Maven
+    #[arg(long, value_name = "VERSION_INDEX")]
+    offline: Option<PathBuf>,
+    /// Leave unresolved %PLACEHOLDER%s in the generated files instead of failing
+    #[arg(long)]
+    allow_unresolved: bool,
+    #[command(flatten)]
+    cache: CacheArgs,
+    #[command(flatten)]
+    options: GeneratorOptions,
+}
+
+/// Command line options for the Maven metadata cache.
+#[derive(clap::Args)]
+#[command(next_help_heading = "Maven metadata cache")]
+pub struct CacheArgs {
+    /// The cache directory (default: in the user's cache directory)
+    #[arg(long, value_name = "DIR")]
+    pub cache_dir: Option<PathBuf>,
+    /// How long cached Maven metadata is used before downloading it again
+    #[arg(long, value_name = "SECONDS", default_value_t = 3600)]
+    pub cache_ttl: u64,
+    /// Download all Maven metadata again and update the cache
+    #[arg(long, conflicts_with_all = ["cache_only", "no_cache"])]
+    pub refresh_cache: bool,
+    /// Only use cached Maven metadata and never download it
+    #[arg(long, conflicts_with = "no_cache")]
+    pub cache_only: bool,
+    /// Don't read or write the cache
+    #[arg(long)]
+    pub no_cache: bool,
+}
+
+impl CacheArgs {
+    pub fn create_client(&self) -> Result<MavenClient> {
+        self.create_client_with(false)
+    }
+
+    /// Creates a client that reads the cache but never writes to it.
+    pub fn create_read_only_client(&self) -> Result<MavenClient> {
+        self.create_client_with(true)
+    }
+
+    fn create_client_with(&self, read_only: bool) -> Result<MavenClient> {
+        let client = MavenClient::new(reqwest::ClientBuilder::new().build()?);
+        if self.no_cache {
+            return Ok(client);
+        }
+
+        let policy = if self.refresh_cache {
+            CachePolicy::Refresh
+        } else if self.cache_only {
+            CachePolicy::CacheOnly
+        } else {
+            CachePolicy::MaxAge(Duration::from_secs(self.cache_ttl))
+        };
+
+        match self.cache_dir.clone().or_else(MetadataCache::default_directory) {
+            Some(directory) => {
+                let cache = MetadataCache::new(directory, policy);
+                Ok(client.with_cache(if read_only { cache.read_only() } else { cache }))
+            }
+            None if policy == CachePolicy::CacheOnly => {
+                Err(err!("Could not find a cache directory; specify one with --cache-dir"))
+            }
+            None => Ok(client),
+        }
+    }
+}
+
+// Generator settings that can be passed as flags instead of being prompted for.
+// If all required settings are present, the interactive wizard is skipped entirely.
+#[derive(clap::Args, Default)]
+#[command(next_help_heading = "Generator settings")]
+struct GeneratorOptions {
+    /// Read settings from a TOML or JSON config file; missing settings are prompted for
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+    /// Write the final settings to a TOML or JSON config file
+    #[arg(long, value_name = "FILE")]
+    save_config: Option<PathBuf>,
+    /// The human-readable name of the mod
+    #[arg(long)]
+    mod_name: Option<String>,
+    /// The mod ID (default: derived from the mod name)
+    #[arg(long)]
+    mod_id: Option<String>,
+    /// The name of the main class (default: derived from the mod name)
+    #[arg(long)]
+    main_class_name: Option<String>,
+    /// The package name of the mod
+    #[arg(long)]
+    package_name: Option<String>,
+    /// The Minecraft version
+    #[arg(long)]
+    game_version: Option<String>,
+    /// The project type
+    #[arg(long)]
+    project_type: Option<ProjectType>,
+    /// The mod loaders of a multiplatform project, separated by commas
+    #[arg(long, value_delimiter = ',')]
+    loaders: Option<Vec<Subproject>>,
+    /// Whether to create a Fabric-like subproject (requires Fabric and Quilt)
+    #[arg(long)]
+    fabric_like: Option<bool>,
+    /// Whether to create a Forge-like subproject (requires Forge and NeoForge)
+    #[arg(long)]
+    forge_like: Option<bool>,
+    /// The mappings
+    #[arg(long)]
+    mappings: Option<MappingSet>,
+    /// The programming language (default: Java)
+    #[arg(long)]
+    language: Option<Language>,
+    /// The build toolchain (NeoForge projects only)
+    #[arg(long)]
+    toolchain: Option<Toolchain>,
+    /// Whether to depend on Architectury API (multiplatform projects only)
+    #[arg(long)]
+    architectury_api: Option<bool>,
+    /// Pin or constrain a dependency version, e.g. fabric-loader=0.16.9 or forge=">=52.0.20, <52.1";
+    /// can be repeated
+    #[arg(long = "pin", value_name = "DEPENDENCY=VERSION", value_parser = parse_pin)]
+    pin_args: Vec<(String, VersionConstraint)>,
+    /// Custom Maven repository URLs from the config file and the environment
+    #[arg(skip)]
+    repositories: MavenRepositoryUrls,
+    /// Version pins from the config file
+    #[arg(skip)]
+    pins: VersionPins,
+}
+
+impl GeneratorOptions {
+    /// Fills in settings that weren't passed as flags from a config file.
+    fn apply_config(&mut self, config: PartialGeneratorApp) {
+        self.mod_name = self.mod_name.take().or(config.mod_name);
+        // An empty mod ID means that it's derived from the mod name.
+        self.mod_id = self.mod_id.take().or(config.mod_id.filter(|id| !id.is_empty()));
+        self.main_class_name = self.main_class_name.take()
+            .or(config.main_class_name.filter(|name| !name.is_empty()));
+        self.package_name = self.package_name.take().or(config.package_name);
+        self.game_version = self.game_version.take().or(config.game_version);
+        self.project_type = self.project_type.or(config.project_type);
+        self.mappings = self.mappings.or(config.mapping_set);
+        self.language = self.language.or(config.language);
+        if let Some(repositories) = config.repositories {
+            self.repositories.merge(repositories);
+        }
+        if let Some(pins) = config.pins {
+            self.pins.merge(pins);
+        }
+        if self.project_type.is_none_or(|t| t == ProjectType::NeoForge) {
+            self.toolchain = self.toolchain.or(config.toolchain);
+        }
+
+        // The multiplatform settings are ignored for other project types
+        // since saved configs contain them regardless of the project type.
+        if self.project_type.is_some_and(|t| t != ProjectType::Multiplatform) {
+            return;
+        }
+
+        // Availability of Architectury API is checked against the final loaders when prompting.
+        if let Some(dependencies) = config.dependencies {
+            self.architectury_api = self.architectury_api.or(Some(dependencies.architectury_api));
+        }
+
+        let Some(subprojects) = config.subprojects else {
+            return;
+        };
+
+        // The other multiplatform settings depend on the loaders,
+        // so they're only taken from the config together with its loaders.
+        let loaders: Vec<_> = Subproject::value_variants()
+            .iter()
+            .copied()
+            .filter(|s| s.is_enabled_in(&subprojects))
+            .collect();
+        if self.loaders.is_some() || loaders.is_empty() {
+            return;
+        }
+        self.loaders = Some(loaders);
+        if self.fabric_like.is_none() && subprojects.fabric_likes {
+            self.fabric_like = Some(true);
+        }
+        if self.forge_like.is_none() && subprojects.forge_likes {
+            self.forge_like = Some(true);
+        }
+    }
+
+    /// Checks whether all settings without a default value have been provided.
+    fn has_required(&self) -> bool {
+        self.mod_name.is_some()
+            && self.package_name.is_some()
+            && self.game_version.is_some()
+            && self.mappings.is_some()
+            && match self.project_type {
+                Some(ProjectType::Multiplatform) => self.loaders.is_some(),
+                Some(_) => true,
+                None => false,
+            }
+    }
 }
 
 pub async fn main() -> Result<()> {
     let args = Args::parse();
-    if args.zip {
+    let options = args.options;
+    let mode = if args.allow_unresolved {
+        RenderMode::Lenient
+    } else {
+        RenderMode::Strict
+    };
+    let version_source = if let Some(path) = &args.offline {
+        VersionSource::Offline(load_version_index(path)?)
+    } else {
+        // Dry runs don't write anything to disk, including the cache.
+        VersionSource::Online(if args.dry_run {
+            args.cache.create_read_only_client()?
+        } else {
+            args.cache.create_client()?
+        })
+    };
+
+    if args.dry_run {
+        let default_name = args.output.as_ref()
+            .and_then(|output| output.file_name())
+            .and_then(|s| s.to_str())
+            .map(|s| s.strip_suffix(".zip").unwrap_or(s));
+        dry_run(default_name, options, &version_source, mode).await?
+    } else if args.zip {
         let (file, default_name) = if let Some(output) = &args.output {
             // If the file was provided, try to derive the mod name from it.
             let name = output.file_name()
@@ -40,7 +275,7 @@ pub async fn main() -> Result<()> {
             (FsZipWriteTarget::InDirectory(dir), None)
         };
 
-        run(ZipFilerProvider(file), default_name, |app| {
+        run(ZipFilerProvider(file), default_name, options, &version_source, mode, |app| {
             if let Some(output) = &args.output {
                 output.to_string_lossy().into_owned()
             } else {
@@ -70,7 +305,7 @@ pub async fn main() -> Result<()> {
         }
 
         let default_name = dir.file_name().and_then(|s| s.to_str());
-        run(DirectoryFilerProvider(&dir), default_name, |_| {
+        run(DirectoryFilerProvider(&dir), default_name, options, &version_source, mode, |_| {
             dir.to_string_lossy()
         })
         .await?
@@ -78,61 +313,223 @@ pub async fn main() -> Result<()> {
     Ok(())
 }
 
-async fn run<F, N, D>(filer_provider: F, default_mod_name: Option<&str>, output_name_provider: N) -> Result<()>
+async fn run<F, N, D>(
+    filer_provider: F,
+    default_mod_name: Option<&str>,
+    options: GeneratorOptions,
+    version_source: &VersionSource,
+    mode: RenderMode,
+    output_name_provider: N,
+) -> Result<()>
 where
     F: FilerProvider,
     N: FnOnce(&GeneratorApp) -> D,
     D: std::fmt::Display,
 {
     let version_list = load_minecraft_version_list()?;
-    let app = prompt(default_mod_name, &version_list)?;
+    let configuration = configure(default_mod_name, &version_list, options)?;
+    let app = &configuration.app;
+
     let spinner = spinner();
     spinner.start("Generating...");
-    crate::generator::generate(&app, &version_list, &filer_provider).await?;
+    crate::generator::generate(app, &version_list, version_source, mode, &filer_provider).await?;
     spinner.stop("Done!");
-    outro(format!("Generated into {}!", output_name_provider(&app)))?;
+    let output_name = output_name_provider(app).to_string();
+    // The config is only saved once the project has been generated successfully.
+    configuration.save()?;
+    outro(format!("Generated into {}!", output_name))?;
     Ok(())
 }
 
+async fn dry_run(
+    default_mod_name: Option<&str>,
+    options: GeneratorOptions,
+    version_source: &VersionSource,
+    mode: RenderMode,
+) -> Result<()> {
+    let version_list = load_minecraft_version_list()?;
+    let configuration = configure(default_mod_name, &version_list, options)?;
+    let spinner = spinner();
+    spinner.start("Resolving...");
+    let plan = crate::generator::plan(&configuration.app, &version_list, version_source, mode).await?;
+    spinner.stop("Done!");
+
+    println!("Files in {}:", plan.file_name);
+    let size_width = plan.files.iter()
+        .map(|file| file.size.to_string().len())
+        .max()
+        .unwrap_or(0);
+    for file in &plan.files {
+        println!(
+            "  {:04o}  {:>width$}  {}",
+            file.permissions.unix(),
+            file.size,
+            file.path,
+            width = size_width
+        );
+    }
+
+    println!();
+    println!("Resolved versions:");
+    for (key, value) in &plan.resolved_versions {
+        println!("  {} = {}", key, value);
+    }
+
+    if let Some(path) = &configuration.save_config {
+        println!();
+        println!("Would save the config to {}", path.to_string_lossy());
+    }
+    Ok(())
+}
+
+/// A generator app configured from the config file, the flags and the prompts.
+struct Configuration {
+    app: GeneratorApp,
+    save_config: Option<PathBuf>,
+    /// The repositories from the config file, without the ones from the environment.
+    config_repositories: MavenRepositoryUrls,
+}
+
+impl Configuration {
+    /// Writes the config file if one was requested with `--save-config`.
+    fn save(self) -> Result<()> {
+        let Some(path) = self.save_config else {
+            return Ok(());
+        };
+        // Mirrors from the environment are machine-specific, so they're not saved.
+        let app = GeneratorApp { repositories: self.config_repositories, ..self.app };
+        write_config_file(&path, &app)
+    }
+}
+
+/// Loads the config file and prompts for the missing settings.
+fn configure(default_mod_name: Option<&str>, version_list: &MinecraftVersionList, options: GeneratorOptions) -> Result<Configuration> {
+    let mut options = options;
+    if let Some(path) = &options.config {
+        let config: PartialGeneratorApp = read_config_file(path)?;
+        options.apply_config(config);
+    }
+    options.repositories.validate()?;
+    let env_repositories = MavenRepositoryUrls::from_env()?;
+    // Pins from flags take precedence over the config file.
+    for (dependency, constraint) in std::mem::take(&mut options.pin_args) {
+        options.pins.set(&dependency, constraint)?;
+    }
+    let save_config = options.save_config.clone();
+
+    let mut app = prompt(default_mod_name, version_list, options)?;
+    let config_repositories = app.repositories.clone();
+    // Environment variables take precedence over the config file.
+    app.repositories.merge(env_repositories);
+
+    Ok(Configuration { app, save_config, config_repositories })
+}
+
 fn load_minecraft_version_list() -> Result<MinecraftVersionList> {
     Ok(serde_json::from_str(MINECRAFT_VERSIONS_JSON)?)
 }
 
-fn prompt(default_name: Option<&str>, version_list: &MinecraftVersionList) -> Result<GeneratorApp> {
-    intro("Architectury Template Generator")?;
+fn load_version_index(path: &Path) -> Result<VersionIndex> {
+    let json = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("Could not read version index {}", path.to_string_lossy()))?;
+    serde_json::from_str(&json)
+        .wrap_err_with(|| format!("Could not parse version index {}", path.to_string_lossy()))
+}
 
-    let mut mod_name = input("Mod name");
-    if let Some(name) = default_name {
-        mod_name = mod_name.default_input(name);
+fn prompt(default_name: Option<&str>, version_list: &MinecraftVersionList, options: GeneratorOptions) -> Result<GeneratorApp> {
+    // Only show the wizard if something required is missing.
+    let interactive = !options.has_required();
+    if interactive {
+        intro("Architectury Template Generator")?;
     }
-    let mod_name: String = mod_name.interact()?;
 
-    let mod_id: String = input("Mod ID")
-        .default_input(&crate::mod_ids::to_mod_id(&mod_name))
-        .validate_interactively(ModIdValidate)
-        .interact()?;
+    let mod_name: String = if let Some(name) = options.mod_name {
+        name
+    } else {
+        let mut mod_name = input("Mod name");
+        if let Some(name) = default_name {
+            mod_name = mod_name.default_input(name);
+        }
+        mod_name.interact()?
+    };
 
-    let package_name: String = input("Package name")
-        .interact()?;
+    let mod_id: String = if let Some(mod_id) = options.mod_id {
+        crate::mod_ids::validate_mod_id(&mod_id)
+            .wrap_err_with(|| format!("Invalid mod ID {}", mod_id))?;
+        mod_id
+    } else if interactive {
+        input("Mod ID")
+            .default_input(&crate::mod_ids::to_mod_id(&mod_name))
+            .validate_interactively(ModIdValidate)
+            .interact()?
+    } else {
+        crate::mod_ids::to_mod_id(&mod_name)
+    };
 
-    let mut versions: Vec<_> = version_list.versions.iter()
-        .map(|version| {
-            (version, &version.version, "")
-        })
-        .collect();
-    versions.reverse(); // newest first
-    let game_version = select("Minecraft version")
-        .items(&versions)
-        .interact()?;
-
-    let mapping_sets: Vec<_> = MappingSet::iter()
-        .map(|set| {
-            (set, set.name(), set.description())
-        })
-        .collect();
-    let mapping_set = select("Mappings")
-        .items(&mapping_sets)
-        .interact()?;
+    let main_class_name: String = if let Some(main_class_name) = options.main_class_name {
+        crate::class_names::validate_class_name(&main_class_name)
+            .wrap_err_with(|| format!("Invalid main class name {}", main_class_name))?;
+        main_class_name
+    } else if interactive {
+        input("Main class name")
+            .default_input(&crate::class_names::to_class_name(&mod_name))
+            .validate_interactively(ClassNameValidate)
+            .interact()?
+    } else {
+        let main_class_name = crate::class_names::to_class_name(&mod_name);
+        crate::class_names::validate_class_name(&main_class_name)
+            .wrap_err_with(|| format!("Could not derive a main class name from {}; pass --main-class-name", mod_name))?;
+        main_class_name
+    };
+
+    let package_name: String = if let Some(package_name) = options.package_name {
+        package_name
+    } else {
+        input("Package name")
+            .interact()?
+    };
+
+    let game_version = if let Some(version) = &options.game_version {
+        version_list.versions.iter()
+            .find(|x| &x.version == version)
+            .ok_or_else(|| err!("Unknown Minecraft version {}", version))?
+    } else {
+        let mut versions: Vec<_> = version_list.versions.iter()
+            .map(|version| {
+                (version, &version.version, "")
+            })
+            .collect();
+        versions.reverse(); // newest first
+        select("Minecraft version")
+            .items(&versions)
+            .interact()?
+    };
+
+    let mapping_set = if let Some(mapping_set) = options.mappings {
+        mapping_set
+    } else {
+        let mapping_sets: Vec<_> = MappingSet::iter()
+            .map(|set| {
+                (set, set.name(), set.description())
+            })
+            .collect();
+        select("Mappings")
+            .items(&mapping_sets)
+            .interact()?
+    };
+
+    let language = if let Some(language) = options.language {
+        language
+    } else if interactive {
+        let languages: Vec<_> = Language::iter()
+            .map(|language| (language, language.name(), language.description()))
+            .collect();
+        select("Language")
+            .items(&languages)
+            .interact()?
+    } else {
+        Language::default()
+    };
 
     let mut project_types = vec![
         (ProjectType::Multiplatform, "Multiplatform", ""),
@@ -143,53 +540,174 @@ fn prompt(default_name: Option<&str>, version_list: &MinecraftVersionList) -> Re
     if game_version.neoforge.is_some() {
         project_types.push((ProjectType::NeoForge, "NeoForge", ""));
     }
-    let project_type: ProjectType = select("Project type")
-        .items(&project_types)
-        .interact()?;
+    project_types.push((ProjectType::Fabric, "Fabric", ""));
+    project_types.push((ProjectType::Quilt, "Quilt", ""));
+    let project_type: ProjectType = if let Some(project_type) = options.project_type {
+        if !project_types.iter().any(|(t, _, _)| *t == project_type) {
+            return Err(err!(
+                "Project type {} is not available for Minecraft {}",
+                value_name(&project_type),
+                game_version.version
+            ));
+        }
+        project_type
+    } else {
+        select("Project type")
+            .items(&project_types)
+            .interact()?
+    };
+
+    let toolchain = if project_type == ProjectType::NeoForge {
+        let toolchains: Vec<_> = Toolchain::iter()
+            .filter(|toolchain| toolchain.supports(mapping_set))
+            .map(|toolchain| (toolchain, toolchain.name(), toolchain.description()))
+            .collect();
+        if let Some(toolchain) = options.toolchain {
+            if !toolchain.supports(mapping_set) {
+                return Err(err!("{} can't be used with {}", mapping_set.name(), toolchain.name()));
+            }
+            toolchain
+        } else if interactive && toolchains.len() > 1 {
+            select("Build toolchain")
+                .items(&toolchains)
+                .interact()?
+        } else {
+            Toolchain::default()
+        }
+    } else {
+        if options.toolchain.is_some_and(|toolchain| toolchain != Toolchain::default()) {
+            return Err(err!("A build toolchain can only be chosen for NeoForge projects"));
+        }
+        Toolchain::default()
+    };
 
     let mut subprojects = Subprojects::default();
     let mut dependencies = Dependencies::default();
 
     if project_type == ProjectType::Multiplatform {
-        let mut subproject_options: Vec<_> = vec![
-            (Subproject::Fabric, "Fabric", ""),
-            (Subproject::Forge, "Forge", ""),
-            (Subproject::NeoForge, "NeoForge", ""),
-            (Subproject::Quilt, "Quilt", ""),
-        ];
-        subproject_options.retain(|(s, _, _)| s.is_available_on(&game_version));
-        let chosen_subprojects = multiselect("Mod loaders")
-            .items(&subproject_options)
-            .interact()?;
+        let chosen_subprojects = if let Some(loaders) = options.loaders {
+            if loaders.is_empty() {
+                return Err(err!("At least one mod loader must be chosen"));
+            }
+
+            for loader in &loaders {
+                if !loader.is_available_on(game_version) {
+                    return Err(err!(
+                        "Mod loader {} is not available for Minecraft {}",
+                        value_name(loader),
+                        game_version.version
+                    ));
+                }
+            }
+
+            loaders
+        } else {
+            let mut subproject_options: Vec<_> = vec![
+                (Subproject::Fabric, "Fabric", ""),
+                (Subproject::Forge, "Forge", ""),
+                (Subproject::NeoForge, "NeoForge", ""),
+                (Subproject::Quilt, "Quilt", ""),
+            ];
+            subproject_options.retain(|(s, _, _)| s.is_available_on(game_version));
+            multiselect("Mod loaders")
+                .items(&subproject_options)
+                .interact()?
+        };
 
         for subproject in chosen_subprojects {
             subproject.apply_to(&mut subprojects);
         }
 
-        if subprojects.fabric && subprojects.quilt {
+        if let Some(fabric_like) = options.fabric_like {
+            if fabric_like && !(subprojects.fabric && subprojects.quilt) {
+                return Err(err!("A Fabric-like subproject requires both Fabric and Quilt"));
+            }
+            subprojects.fabric_likes = fabric_like;
+        } else if interactive && subprojects.fabric && subprojects.quilt {
             subprojects.fabric_likes = confirm("Fabric-like subproject (shared code between Fabric and Quilt)?")
                 .initial_value(subprojects.fabric_likes)
                 .interact()?;
         }
 
-        dependencies.architectury_api = confirm("Architectury API?")
-            .initial_value(dependencies.architectury_api)
-            .interact()?;
+        if let Some(forge_like) = options.forge_like {
+            if forge_like && !(subprojects.forge && subprojects.neoforge) {
+                return Err(err!("A Forge-like subproject requires both Forge and NeoForge"));
+            }
+            subprojects.forge_likes = forge_like;
+        } else if interactive && subprojects.forge && subprojects.neoforge {
+            subprojects.forge_likes = confirm("Forge-like subproject (shared code between Forge and NeoForge)?")
+                .initial_value(subprojects.forge_likes)
+                .interact()?;
+        }
+
+        let architectury_api_available = subprojects.supports_architectury_api(game_version);
+        if let Some(architectury_api) = options.architectury_api {
+            if architectury_api && !architectury_api_available {
+                return Err(err!(
+                    "Architectury API is not available for this project on Minecraft {}",
+                    game_version.version
+                ));
+            }
+            dependencies.architectury_api = architectury_api;
+        } else if !architectury_api_available {
+            dependencies.architectury_api = false;
+        } else if interactive {
+            dependencies.architectury_api = confirm("Architectury API?")
+                .initial_value(dependencies.architectury_api)
+                .interact()?;
+        }
+    } else {
+        if options.loaders.is_some() {
+            return Err(err!("Mod loaders can only be chosen for multiplatform projects"));
+        }
+
+        if options.fabric_like == Some(true) {
+            return Err(err!("A Fabric-like subproject can only be created in multiplatform projects"));
+        }
+
+        if options.forge_like == Some(true) {
+            return Err(err!("A Forge-like subproject can only be created in multiplatform projects"));
+        }
+
+        if options.architectury_api == Some(true) {
+            return Err(err!("Architectury API can only be added to multiplatform projects"));
+        }
     }
 
     let generator = GeneratorApp {
         mod_name,
         mod_id,
+        main_class_name,
         package_name,
         game_version: game_version.version.clone(),
         project_type,
         subprojects,
         mapping_set,
-        dependencies
+        toolchain,
+        language,
+        dependencies,
+        repositories: options.repositories,
+        pins: options.pins,
     };
     Ok(generator)
 }
 
+/// Returns the command line name of a value.
+fn value_name<V: ValueEnum>(value: &V) -> String {
+    value.to_possible_value()
+        .map(|value| value.get_name().to_owned())
+        .unwrap_or_default()
+}
+
+fn parse_pin(input: &str) -> std::result::Result<(String, VersionConstraint), String> {
+    let (dependency, version) = input.split_once('=')
+        .ok_or_else(|| "expected DEPENDENCY=VERSION".to_owned())?;
+    let constraint = VersionConstraint::parse(version).map_err(|err| err.to_string())?;
+    // Check that the dependency exists.
+    VersionPins::default().set(dependency, constraint.clone()).map_err(|err| err.to_string())?;
+    Ok((dependency.to_owned(), constraint))
+}
+
 fn get_current_dir() -> Result<PathBuf> {
     std::env::current_dir()
         .wrap_err("Couldn't get current directory")
@@ -206,10 +724,21 @@ impl cliclack::Validate<String> for ModIdValidate {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+struct ClassNameValidate;
+
+impl cliclack::Validate<String> for ClassNameValidate {
+    type Err = crate::result::Error;
+
+    fn validate(&self, input: &String) -> Result<()> {
+        crate::class_names::validate_class_name(input)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
 enum Subproject {
     Fabric,
     Forge,
+    #[value(name = "neoforge")]
     NeoForge,
     Quilt,
 }
@@ -223,6 +752,15 @@ impl Subproject {
         }
     }
 
+    pub fn is_enabled_in(&self, settings: &crate::Subprojects) -> bool {
+        match self {
+            Self::Fabric => settings.fabric,
+            Self::Forge => settings.forge,
+            Self::NeoForge => settings.neoforge,
+            Self::Quilt => settings.quilt,
+        }
+    }
+
     pub fn apply_to(&self, settings: &mut crate::Subprojects) {
         match self {
             Self::Fabric => settings.fabric = true,
@@ -232,3 +770,283 @@ impl Subproject {
         }
     }
 }
+
+impl ValueEnum for ProjectType {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Multiplatform, Self::NeoForge, Self::Forge, Self::Fabric, Self::Quilt]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Multiplatform => PossibleValue::new("multiplatform"),
+            Self::NeoForge => PossibleValue::new("neoforge"),
+            Self::Forge => PossibleValue::new("forge"),
+            Self::Fabric => PossibleValue::new("fabric"),
+            Self::Quilt => PossibleValue::new("quilt"),
+        })
+    }
+}
+
+impl ValueEnum for Toolchain {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::ArchitecturyLoom, Self::ModDevGradle]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::ArchitecturyLoom => PossibleValue::new("architectury-loom").help(self.name()),
+            Self::ModDevGradle => PossibleValue::new("moddevgradle").help(self.name()),
+        })
+    }
+}
+
+impl ValueEnum for Language {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Java, Self::Kotlin]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Java => PossibleValue::new("java").help(self.name()),
+            Self::Kotlin => PossibleValue::new("kotlin").help(self.name()),
+        })
+    }
+}
+
+impl ValueEnum for MappingSet {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Mojang, Self::MojangParchment, Self::Yarn]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::Mojang => PossibleValue::new("mojang").help(self.name()),
+            Self::MojangParchment => PossibleValue::new("mojang-parchment").help(self.name()),
+            Self::Yarn => PossibleValue::new("yarn").help(self.name()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_options(args: &[&str]) -> GeneratorOptions {
+        let mut full_args = vec!["architectury-template-generator"];
+        full_args.extend_from_slice(args);
+        Args::try_parse_from(full_args).unwrap().options
+    }
+
+    fn prompt_with(args: &[&str]) -> Result<GeneratorApp> {
+        let version_list = load_minecraft_version_list()?;
+        prompt(None, &version_list, parse_options(args))
+    }
+
+    #[test]
+    fn complete_flags_skip_the_wizard() {
+        let app = prompt_with(&[
+            "--mod-name", "My Mod",
+            "--package-name", "com.example.mymod",
+            "--game-version", "1.20.1",
+            "--project-type", "multiplatform",
+            "--loaders", "fabric,forge,quilt",
+            "--fabric-like", "true",
+            "--mappings", "yarn",
+        ]).unwrap();
+
+        assert_eq!(app.mod_id, "my_mod");
+        assert!(app.subprojects.fabric && app.subprojects.forge && app.subprojects.quilt);
+        assert!(!app.subprojects.neoforge);
+        assert!(app.subprojects.fabric_likes);
+        assert!(app.mapping_set == MappingSet::Yarn);
+        assert!(app.language == Language::Java);
+        assert!(app.dependencies.architectury_api);
+    }
+
+    #[test]
+    fn language_flag() {
+        let app = prompt_with(&[
+            "--mod-name", "My Mod",
+            "--package-name", "com.example.mymod",
+            "--game-version", "1.21.1",
+            "--project-type", "fabric",
+            "--mappings", "mojang",
+            "--language", "kotlin",
+        ]).unwrap();
+        assert!(app.language == Language::Kotlin);
+    }
+
+    #[test]
+    fn flags_override_config() {
+        let mut options = parse_options(&["--game-version", "1.21.1", "--loaders", "neoforge"]);
+        let config: PartialGeneratorApp = crate::config::ConfigFormat::Toml.parse(r#"
+            mod_name = "House Mod"
+            package_name = "com.example.house"
+            game_version = "1.20.1"
+            project_type = "Multiplatform"
+            mapping_set = "Mojang"
+
+            [subprojects]
+            fabric = true
+            forge = true
+        "#).unwrap();
+        options.apply_config(config);
+
+        let version_list = load_minecraft_version_list().unwrap();
+        let app = prompt(None, &version_list, options).unwrap();
+        assert_eq!(app.mod_name, "House Mod");
+        assert_eq!(app.game_version, "1.21.1");
+        assert!(app.subprojects.neoforge && !app.subprojects.fabric && !app.subprojects.forge);
+    }
+
+    fn apply_house_config(args: &[&str]) -> Result<GeneratorApp> {
+        let mut options = parse_options(args);
+        let config: PartialGeneratorApp = crate::config::ConfigFormat::Toml.parse(r#"
+            mod_name = "House Mod"
+            package_name = "com.example.house"
+            game_version = "1.20.1"
+            project_type = "Multiplatform"
+            mapping_set = "Mojang"
+
+            [subprojects]
+            fabric = true
+            quilt = true
+            fabric_likes = true
+
+            [dependencies]
+            architectury_api = false
+        "#).unwrap();
+        options.apply_config(config);
+        prompt(None, &load_minecraft_version_list()?, options)
+    }
+
+    #[test]
+    fn project_type_flag_drops_config_loaders() {
+        let app = apply_house_config(&["--project-type", "forge"]).unwrap();
+        assert!(app.project_type == ProjectType::Forge);
+        assert!(!app.subprojects.fabric && !app.subprojects.fabric_likes);
+    }
+
+    #[test]
+    fn loaders_flag_drops_config_subproject_settings() {
+        let app = apply_house_config(&["--loaders", "fabric,forge"]).unwrap();
+        assert!(app.subprojects.fabric && app.subprojects.forge && !app.subprojects.quilt);
+        assert!(!app.subprojects.fabric_likes);
+        assert!(!app.dependencies.architectury_api);
+    }
+
+    #[test]
+    fn loaders_flag_drops_config_forge_like() {
+        let mut options = parse_options(&["--loaders", "fabric"]);
+        let config: PartialGeneratorApp = crate::config::ConfigFormat::Toml.parse(r#"
+            mod_name = "Anvil Mod"
+            package_name = "com.example.anvil"
+            game_version = "1.20.4"
+            project_type = "Multiplatform"
+            mapping_set = "Mojang"
+
+            [subprojects]
+            forge = true
+            neoforge = true
+            forge_likes = true
+        "#).unwrap();
+        options.apply_config(config);
+        let app = prompt(None, &load_minecraft_version_list().unwrap(), options).unwrap();
+        assert!(app.subprojects.fabric && !app.subprojects.forge && !app.subprojects.neoforge);
+        assert!(!app.subprojects.forge_likes);
+    }
+
+    #[test]
+    fn project_type_flag_drops_config_toolchain() {
+        let mut options = parse_options(&["--project-type", "fabric"]);
+        let config: PartialGeneratorApp = crate::config::ConfigFormat::Toml.parse(r#"
+            mod_name = "Tool Mod"
+            package_name = "com.example.tool"
+            game_version = "1.21.1"
+            mapping_set = "Mojang"
+            toolchain = "ModDevGradle"
+        "#).unwrap();
+        options.apply_config(config);
+        let app = prompt(None, &load_minecraft_version_list().unwrap(), options).unwrap();
+        assert!(app.project_type == ProjectType::Fabric);
+        assert!(app.toolchain == Toolchain::ArchitecturyLoom);
+    }
+
+    #[test]
+    fn saved_config_leaves_out_environment_repositories() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("house.toml");
+        let mut app = apply_house_config(&[]).unwrap();
+        let config_repositories = MavenRepositoryUrls {
+            fabric: vec!["https://maven.example.com/fabric".to_owned()],
+            ..Default::default()
+        };
+        app.repositories = config_repositories.clone();
+        app.repositories.merge(MavenRepositoryUrls {
+            forge: vec!["https://mirror.example.com/forge".to_owned()],
+            ..Default::default()
+        });
+
+        let configuration = Configuration { app, save_config: Some(path.clone()), config_repositories };
+        configuration.save().unwrap();
+        let saved: PartialGeneratorApp = read_config_file(&path).unwrap();
+        let repositories = saved.repositories.unwrap();
+        assert_eq!(repositories.fabric, ["https://maven.example.com/fabric"]);
+        assert!(repositories.forge.is_empty());
+    }
+
+    #[test]
+    fn unavailable_project_type_is_rejected() {
+        let result = prompt_with(&[
+            "--mod-name", "My Mod",
+            "--package-name", "com.example",
+            "--game-version", "1.21.1",
+            "--project-type", "forge",
+            "--mappings", "mojang",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn forge_like_requires_forge_and_neoforge() {
+        let args = [
+            "--mod-name", "My Mod",
+            "--package-name", "com.example",
+            "--game-version", "1.20.4",
+            "--project-type", "multiplatform",
+            "--forge-like", "true",
+            "--mappings", "mojang",
+        ];
+        let app = prompt_with(&[&args[..], &["--loaders", "forge,neoforge"]].concat()).unwrap();
+        assert!(app.subprojects.forge_likes);
+        assert!(prompt_with(&[&args[..], &["--loaders", "fabric,neoforge"]].concat()).is_err());
+    }
+
+    #[test]
+    fn moddevgradle_requires_mojang_mappings() {
+        let args = [
+            "--mod-name", "My Mod",
+            "--package-name", "com.example",
+            "--game-version", "1.21.1",
+            "--project-type", "neoforge",
+            "--toolchain", "moddevgradle",
+        ];
+        let app = prompt_with(&[&args[..], &["--mappings", "mojang-parchment"]].concat()).unwrap();
+        assert!(app.toolchain == Toolchain::ModDevGradle);
+        assert!(prompt_with(&[&args[..], &["--mappings", "yarn"]].concat()).is_err());
+    }
+
+    #[test]
+    fn fabric_like_requires_fabric_and_quilt() {
+        let result = prompt_with(&[
+            "--mod-name", "My Mod",
+            "--package-name", "com.example",
+            "--game-version", "1.21.1",
+            "--project-type", "multiplatform",
+            "--loaders", "fabric,neoforge",
+            "--fabric-like", "true",
+            "--mappings", "mojang",
+        ]);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,127 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::maven::MavenRepositoryUrls;
+use crate::versions::pins::VersionPins;
+use crate::{Dependencies, Language, MappingSet, ProjectType, Result, Subprojects, Toolchain};
+use crate::result::ResultContext;
+
+/// A generator config where any setting can be left out.
+///
+/// The format matches the serialized form of [`GeneratorApp`](crate::GeneratorApp).
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PartialGeneratorApp {
+    pub mod_name: Option<String>,
+    pub mod_id: Option<String>,
+    pub main_class_name: Option<String>,
+    pub package_name: Option<String>,
+    pub game_version: Option<String>,
+    pub project_type: Option<ProjectType>,
+    pub subprojects: Option<Subprojects>,
+    pub mapping_set: Option<MappingSet>,
+    pub toolchain: Option<Toolchain>,
+    pub language: Option<Language>,
+    pub dependencies: Option<Dependencies>,
+    pub repositories: Option<MavenRepositoryUrls>,
+    pub pins: Option<VersionPins>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+}
+
+impl ConfigFormat {
+    /// Determines the config format from the file extension.
+    /// Files without a `.json` extension are treated as TOML.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Self::Json,
+            _ => Self::Toml,
+        }
+    }
+
+    pub fn parse<T: DeserializeOwned>(&self, text: &str) -> Result<T> {
+        match self {
+            Self::Json => Ok(serde_json::from_str(text)?),
+            Self::Toml => Ok(toml::from_str(text)?),
+        }
+    }
+
+    pub fn write<T: Serialize>(&self, value: &T) -> Result<String> {
+        match self {
+            Self::Json => Ok(serde_json::to_string_pretty(value)? + "\n"),
+            Self::Toml => Ok(toml::to_string_pretty(value)?),
+        }
+    }
+}
+
+pub fn read_config_file<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let text = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("Could not read config file {}", path.to_string_lossy()))?;
+    ConfigFormat::from_path(path)
+        .parse(&text)
+        .wrap_err_with(|| format!("Could not parse config file {}", path.to_string_lossy()))
+}
+
+pub fn write_config_file<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let text = ConfigFormat::from_path(path).write(value)?;
+    std::fs::write(path, text)
+        .wrap_err_with(|| format!("Could not write config file {}", path.to_string_lossy()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GeneratorApp;
+
+    #[test]
+    fn partial_toml_config() {
+        let config: PartialGeneratorApp = ConfigFormat::Toml.parse(r#"
+            package_name = "com.example"
+            mapping_set = "Yarn"
+
+            [subprojects]
+            fabric = true
+        "#).unwrap();
+
+        assert_eq!(config.package_name.as_deref(), Some("com.example"));
+        assert!(config.mapping_set == Some(MappingSet::Yarn));
+        assert!(config.mod_name.is_none());
+        let subprojects = config.subprojects.unwrap();
+        assert!(subprojects.fabric && !subprojects.quilt);
+    }
+
+    #[test]
+    fn round_trip() {
+        let app = GeneratorApp {
+            mod_name: "My Mod".to_owned(),
+            mod_id: "my_mod".to_owned(),
+            main_class_name: String::new(),
+            package_name: "com.example".to_owned(),
+            game_version: "1.21.1".to_owned(),
+            project_type: ProjectType::Multiplatform,
+            subprojects: Subprojects { fabric: true, neoforge: true, ..Default::default() },
+            mapping_set: MappingSet::Mojang,
+            toolchain: Toolchain::ArchitecturyLoom,
+            language: Language::Kotlin,
+            dependencies: Dependencies::default(),
+            repositories: Default::default(),
+            pins: Default::default(),
+        };
+
+        for format in [ConfigFormat::Json, ConfigFormat::Toml] {
+            let text = format.write(&app).unwrap();
+            let parsed: GeneratorApp = format.parse(&text).unwrap();
+            assert_eq!(parsed.mod_id, app.mod_id);
+            assert!(parsed.subprojects.fabric && parsed.subprojects.neoforge);
+        }
+    }
+}
@@ -2,9 +2,12 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::Result;
+use crate::app::generator::RenderedFile;
+use crate::{err, Result};
+use bytes::Bytes;
 use std::collections::HashSet;
 use std::io::{Cursor, Seek, Write};
+use std::sync::Mutex;
 use zip::write::SimpleFileOptions;
 
 // Platform impls
@@ -25,6 +28,7 @@ pub trait FilerProvider {
         F: FnOnce(&mut dyn Filer) -> Result<()>;
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum FilePermissions {
     None,
     Execute,
@@ -39,6 +43,51 @@ impl FilePermissions {
     }
 }
 
+/// A filer that keeps the saved files in memory, in the order they were saved.
+#[derive(Default)]
+pub struct MemoryFiler {
+    pub file_name: Option<String>,
+    pub files: Vec<RenderedFile>,
+}
+
+impl Filer for MemoryFiler {
+    fn set_file_name(&mut self, file_name: String) {
+        self.file_name = Some(file_name)
+    }
+
+    fn save(&mut self, path: &str, content: &[u8], permissions: &FilePermissions) -> Result<()> {
+        self.files.push(RenderedFile {
+            path: path.to_owned(),
+            content: Bytes::copy_from_slice(content),
+            permissions: *permissions,
+        });
+        Ok(())
+    }
+}
+
+/// Provides a [`MemoryFiler`] whose files can be taken with [`MemoryFilerProvider::into_filer`].
+#[derive(Default)]
+pub struct MemoryFilerProvider(Mutex<MemoryFiler>);
+
+impl MemoryFilerProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn into_filer(self) -> MemoryFiler {
+        self.0.into_inner().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl FilerProvider for MemoryFilerProvider {
+    async fn use_filer<F>(&self, block: F) -> Result<()>
+    where
+        F: FnOnce(&mut dyn Filer) -> Result<()> {
+        let mut filer = self.0.lock().map_err(|_| err!("Memory filer was poisoned"))?;
+        block(&mut *filer)
+    }
+}
+
 pub struct ZipFiler<'a, W>
 where
     W: Write + Seek,
@@ -6,12 +6,59 @@ use crate::versions::MinecraftVersion;
 use crate::xml::{read_node, XmlNode};
 use crate::{err, Result};
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use strum::{EnumIter, IntoEnumIterator};
 
-const FABRIC_MAVEN: &'static str = "https://maven.fabricmc.net";
-const ARCHITECTURY_MAVEN: &'static str = "https://maven.architectury.dev";
-const FORGE_MAVEN: &'static str = "https://maven.minecraftforge.net";
-const NEOFORGE_MAVEN: &'static str = "https://maven.neoforged.net/releases";
-const QUILT_MAVEN: &'static str = "https://maven.quiltmc.org/repository/release/";
+#[cfg(not(target_family = "wasm"))]
+pub mod cache;
+
+const FABRIC_MAVEN: &str = "https://maven.fabricmc.net";
+const ARCHITECTURY_MAVEN: &str = "https://maven.architectury.dev";
+const FORGE_MAVEN: &str = "https://maven.minecraftforge.net";
+const NEOFORGE_MAVEN: &str = "https://maven.neoforged.net/releases";
+const QUILT_MAVEN: &str = "https://maven.quiltmc.org/repository/release/";
+const PARCHMENT_MAVEN: &str = "https://maven.parchmentmc.org";
+const KOTLIN_FOR_FORGE_MAVEN: &str = "https://thedarkcolour.github.io/KotlinForForge";
+
+/// An HTTP client for downloading Maven metadata, with an optional metadata cache on native platforms.
+#[derive(Clone)]
+pub struct MavenClient {
+    http: Client,
+    repositories: Arc<MavenRepositoryUrls>,
+    #[cfg(not(target_family = "wasm"))]
+    cache: Option<Arc<cache::MetadataCache>>,
+}
+
+impl MavenClient {
+    pub fn new(http: Client) -> Self {
+        Self {
+            http,
+            repositories: Default::default(),
+            #[cfg(not(target_family = "wasm"))]
+            cache: None,
+        }
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    pub fn with_cache(self, cache: cache::MetadataCache) -> Self {
+        Self {
+            cache: Some(Arc::new(cache)),
+            ..self
+        }
+    }
+
+    pub fn with_repositories(self, repositories: MavenRepositoryUrls) -> Self {
+        Self {
+            repositories: Arc::new(repositories),
+            ..self
+        }
+    }
+
+    pub fn http(&self) -> &Client {
+        &self.http
+    }
+}
 
 pub struct MavenLibrary {
     repository: MavenRepository,
@@ -80,6 +127,10 @@ impl MavenLibrary {
         Self::new(MavenRepository::NeoForge, "net.neoforged", "neoforge")
     }
 
+    pub fn moddevgradle() -> Self {
+        Self::new(MavenRepository::NeoForge, "net.neoforged", "moddev-gradle")
+    }
+
     // Quilt libraries
     pub fn quilt_loader() -> Self {
         Self::new(MavenRepository::Quilt, "org.quiltmc", "quilt-loader")
@@ -88,6 +139,28 @@ impl MavenLibrary {
     pub fn quilted_fabric_api() -> Self {
         Self::new(MavenRepository::Quilt, "org.quiltmc.quilted-fabric-api", "quilted-fabric-api")
     }
+
+    // Kotlin language adapters
+    pub fn fabric_language_kotlin() -> Self {
+        Self::new(MavenRepository::Fabric, "net.fabricmc", "fabric-language-kotlin")
+    }
+
+    pub fn kotlin_for_forge() -> Self {
+        Self::new(MavenRepository::KotlinForForge, "thedarkcolour", "kotlinforforge")
+    }
+
+    pub fn kotlin_for_forge_neoforge() -> Self {
+        Self::new(MavenRepository::KotlinForForge, "thedarkcolour", "kotlinforforge-neoforge")
+    }
+
+    // Parchment libraries
+    pub fn parchment(game_version: &MinecraftVersion) -> Self {
+        Self::new(
+            MavenRepository::Parchment,
+            "org.parchmentmc.data",
+            format!("parchment-{}", game_version.version),
+        )
+    }
 }
 
 impl std::fmt::Display for MavenLibrary {
@@ -102,15 +175,31 @@ impl std::fmt::Display for MavenLibrary {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, EnumIter)]
 pub enum MavenRepository {
     Fabric,
     Architectury,
     Forge,
     NeoForge,
     Quilt,
+    Parchment,
+    KotlinForForge,
 }
 
 impl MavenRepository {
+    /// The key of this repository in config files and environment variables.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Fabric => "fabric",
+            Self::Architectury => "architectury",
+            Self::Forge => "forge",
+            Self::NeoForge => "neoforge",
+            Self::Quilt => "quilt",
+            Self::Parchment => "parchment",
+            Self::KotlinForForge => "kotlin_for_forge",
+        }
+    }
+
     pub fn url(&self) -> &'static str {
         match self {
             Self::Fabric => FABRIC_MAVEN,
@@ -118,19 +207,197 @@ impl MavenRepository {
             Self::Forge => FORGE_MAVEN,
             Self::NeoForge => NEOFORGE_MAVEN,
             Self::Quilt => QUILT_MAVEN,
+            Self::Parchment => PARCHMENT_MAVEN,
+            Self::KotlinForForge => KOTLIN_FOR_FORGE_MAVEN,
         }
     }
 }
 
-async fn download_maven_metadata(client: &Client, library: &MavenLibrary) -> Result<impl XmlNode> {
+/// Custom URLs for Maven repositories, such as internal mirrors.
+///
+/// Each repository can have multiple URLs that are tried in order.
+/// Repositories without custom URLs use their default URL.
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MavenRepositoryUrls {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fabric: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub architectury: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub forge: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub neoforge: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub quilt: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub parchment: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub kotlin_for_forge: Vec<String>,
+}
+
+impl MavenRepositoryUrls {
+    /// The prefix of the environment variables containing custom repository URLs,
+    /// e.g. `ARCHITECTURY_TEMPLATE_MAVEN_FABRIC`.
+    pub const ENV_PREFIX: &'static str = "ARCHITECTURY_TEMPLATE_MAVEN_";
+
+    pub fn is_empty(&self) -> bool {
+        MavenRepository::iter().all(|repository| self.custom_urls(repository).is_empty())
+    }
+
+    /// Returns the custom URLs of the repository.
+    pub fn custom_urls(&self, repository: MavenRepository) -> &[String] {
+        match repository {
+            MavenRepository::Fabric => &self.fabric,
+            MavenRepository::Architectury => &self.architectury,
+            MavenRepository::Forge => &self.forge,
+            MavenRepository::NeoForge => &self.neoforge,
+            MavenRepository::Quilt => &self.quilt,
+            MavenRepository::Parchment => &self.parchment,
+            MavenRepository::KotlinForForge => &self.kotlin_for_forge,
+        }
+    }
+
+    fn custom_urls_mut(&mut self, repository: MavenRepository) -> &mut Vec<String> {
+        match repository {
+            MavenRepository::Fabric => &mut self.fabric,
+            MavenRepository::Architectury => &mut self.architectury,
+            MavenRepository::Forge => &mut self.forge,
+            MavenRepository::NeoForge => &mut self.neoforge,
+            MavenRepository::Quilt => &mut self.quilt,
+            MavenRepository::Parchment => &mut self.parchment,
+            MavenRepository::KotlinForForge => &mut self.kotlin_for_forge,
+        }
+    }
+
+    /// Returns the URLs to try for the repository in order.
+    pub fn urls(&self, repository: MavenRepository) -> Vec<&str> {
+        let custom = self.custom_urls(repository);
+        if custom.is_empty() {
+            vec![repository.url()]
+        } else {
+            custom.iter().map(String::as_str).collect()
+        }
+    }
+
+    /// Replaces the URLs of each repository that has custom URLs in `other`.
+    pub fn merge(&mut self, other: MavenRepositoryUrls) {
+        for repository in MavenRepository::iter() {
+            let urls = other.custom_urls(repository);
+            if !urls.is_empty() {
+                *self.custom_urls_mut(repository) = urls.to_vec();
+            }
+        }
+    }
+
+    /// Checks that all custom URLs are valid HTTP(S) URLs.
+    pub fn validate(&self) -> Result<()> {
+        for repository in MavenRepository::iter() {
+            for url in self.custom_urls(repository) {
+                let parsed = reqwest::Url::parse(url)
+                    .map_err(|e| err!("Invalid URL {} for {} Maven: {}", url, repository.key(), e))?;
+                if !matches!(parsed.scheme(), "http" | "https") || url.contains('\'') {
+                    return Err(err!("Invalid URL {} for {} Maven", url, repository.key()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads custom repository URLs from comma-separated lists in environment variables.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn from_env() -> Result<Self> {
+        let mut urls = Self::default();
+
+        for repository in MavenRepository::iter() {
+            let key = format!("{}{}", Self::ENV_PREFIX, repository.key().to_uppercase());
+            if let Ok(value) = std::env::var(&key) {
+                *urls.custom_urls_mut(repository) = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|url| !url.is_empty())
+                    .map(str::to_owned)
+                    .collect();
+            }
+        }
+
+        urls.validate()?;
+        Ok(urls)
+    }
+}
+
+/// The error returned when none of the repositories have the Maven metadata of a library.
+#[derive(Debug)]
+pub struct MissingMetadata {
+    pub library: String,
+    errors: Vec<String>,
+}
+
+impl std::fmt::Display for MissingMetadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Could not download Maven metadata for {}: {}", self.library, self.errors.join("; "))
+    }
+}
+
+impl std::error::Error for MissingMetadata {}
+
+/// The error returned when a file doesn't exist on the server.
+#[derive(Debug)]
+struct NotFound(String);
+
+impl std::fmt::Display for NotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Could not download {}: got status code {}", self.0, reqwest::StatusCode::NOT_FOUND)
+    }
+}
+
+impl std::error::Error for NotFound {}
+
+async fn download_maven_metadata(client: &MavenClient, library: &MavenLibrary) -> Result<impl XmlNode> {
+    let mut errors: Vec<String> = Vec::new();
+    let mut missing = true;
+
+    for repository_url in client.repositories.urls(*library.repository()) {
+        match download_maven_metadata_from(client, repository_url, library).await {
+            Ok(text) => return read_node(text.as_str()),
+            Err(error) => {
+                missing &= error.downcast_ref::<NotFound>().is_some();
+                errors.push(error.to_string());
+            }
+        }
+    }
+
+    if missing && !errors.is_empty() {
+        return Err(MissingMetadata { library: library.to_string(), errors }.into());
+    }
+    Err(err!("Could not download Maven metadata for {}: {}", library, errors.join("; ")))
+}
+
+async fn download_maven_metadata_from(client: &MavenClient, repository_url: &str, library: &MavenLibrary) -> Result<String> {
     let url = format!(
         "{}/{}/{}/maven-metadata.xml",
-        library.repository().url(),
+        repository_url.trim_end_matches('/'),
         library.group().replace(".", "/"),
         library.name()
     );
-    let response = client.get(&url).send().await?;
 
+    #[cfg(not(target_family = "wasm"))]
+    if let Some(cache) = &client.cache {
+        return cache
+            .get_or_download(repository_url, library, || download_text(&client.http, &url))
+            .await;
+    }
+
+    download_text(&client.http, &url).await
+}
+
+async fn download_text(client: &Client, url: &str) -> Result<String> {
+    let response = client.get(url).send().await?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(NotFound(url.to_owned()).into());
+    }
     if !response.status().is_success() {
         return Err(err!(
             "Could not download {}: got status code {}",
@@ -139,52 +406,77 @@ async fn download_maven_metadata(client: &Client, library: &MavenLibrary) -> Res
         ));
     }
 
-    let text = response.text().await?;
-    read_node(text.as_str())
-}
-
-fn get_latest_version<N: XmlNode>(node: &N) -> Option<String> {
-    node.get_first_child("metadata")?
-        .get_first_child("versioning")?
-        .get_first_child("latest")?
-        .text()
-}
-
-fn get_latest_version_matching<N, F>(node: &N, filter: F) -> Option<String>
-where
-    N: XmlNode,
-    F: Fn(&str) -> bool,
-{
-    let mut matching: Vec<String> = node
-        .get_first_child("metadata")?
-        .get_first_child("versioning")?
-        .get_first_child("versions")?
-        .get_children("version")
-        .filter_map(|child| child.text())
-        .filter(|version| filter(version.as_str()))
-        .collect();
-    matching.sort_by(|a, b| flexver_rs::compare(a.as_str(), b.as_str()).reverse());
-    matching.first().cloned()
-}
-
-pub async fn resolve_matching_version<F>(
-    client: &reqwest::Client,
-    library: MavenLibrary,
-    filter: F,
-) -> Result<String>
-where
-    F: Fn(&str) -> bool,
-{
-    let metadata = download_maven_metadata(client, &library).await?;
-    get_latest_version_matching(&metadata, filter)
-        .ok_or_else(|| err!("Could not find latest version for {}", library))
-}
-
-pub async fn resolve_latest_version(
-    client: &reqwest::Client,
-    library: MavenLibrary,
-) -> Result<String> {
-    let metadata = download_maven_metadata(client, &library).await?;
-    get_latest_version(&metadata)
-        .ok_or_else(|| err!("Could not find latest version for {}", library))
+    Ok(response.text().await?)
+}
+
+/// The versions listed in the Maven metadata of a library.
+pub struct MavenVersions {
+    latest: Option<String>,
+    /// All versions, newest first.
+    versions: Vec<String>,
+}
+
+impl MavenVersions {
+    pub async fn download(client: &MavenClient, library: &MavenLibrary) -> Result<Self> {
+        let metadata = download_maven_metadata(client, library).await?;
+        Ok(Self::read(&metadata))
+    }
+
+    fn read<N: XmlNode>(node: &N) -> Self {
+        let versioning = node
+            .get_first_child("metadata")
+            .and_then(|metadata| metadata.get_first_child("versioning"));
+        let latest = versioning.as_ref()
+            .and_then(|versioning| versioning.get_first_child("latest"))
+            .and_then(|latest| latest.text());
+        let mut versions: Vec<String> = versioning.as_ref()
+            .and_then(|versioning| versioning.get_first_child("versions"))
+            .map(|versions| versions.get_children("version").filter_map(|child| child.text()).collect())
+            .unwrap_or_default();
+        versions.sort_by(|a, b| flexver_rs::compare(a.as_str(), b.as_str()).reverse());
+        Self { latest, versions }
+    }
+
+    pub fn latest(&self) -> Option<&str> {
+        self.latest.as_deref()
+    }
+
+    /// Finds the latest version matching the filter.
+    pub fn find_matching<F>(&self, filter: F) -> Option<&str>
+    where
+        F: Fn(&str) -> bool,
+    {
+        self.versions.iter().map(String::as_str).find(|version| filter(version))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_urls_replace_defaults() {
+        let mut urls = MavenRepositoryUrls {
+            fabric: vec!["https://a.example/fabric".to_owned()],
+            forge: vec!["https://a.example/forge".to_owned()],
+            ..Default::default()
+        };
+        urls.merge(MavenRepositoryUrls {
+            fabric: vec!["https://b.example/fabric".to_owned(), "https://maven.fabricmc.net".to_owned()],
+            ..Default::default()
+        });
+
+        assert_eq!(urls.urls(MavenRepository::Fabric), vec!["https://b.example/fabric", "https://maven.fabricmc.net"]);
+        assert_eq!(urls.urls(MavenRepository::Forge), vec!["https://a.example/forge"]);
+        assert_eq!(urls.urls(MavenRepository::Quilt), vec![QUILT_MAVEN]);
+    }
+
+    #[test]
+    fn invalid_urls_are_rejected() {
+        let urls = MavenRepositoryUrls {
+            neoforge: vec!["file:///srv/maven".to_owned()],
+            ..Default::default()
+        };
+        assert!(urls.validate().is_err());
+    }
 }
@@ -0,0 +1,221 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::MavenLibrary;
+use crate::result::ResultContext;
+use crate::{err, Result};
+
+/// Controls when cached Maven metadata is used instead of downloading it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CachePolicy {
+    /// Use cached metadata younger than the given age, and download it otherwise.
+    MaxAge(Duration),
+    /// Always download the metadata and update the cache.
+    Refresh,
+    /// Only use cached metadata and never download it.
+    CacheOnly,
+}
+
+/// A persistent cache of `maven-metadata.xml` files,
+/// keyed by repository, group and artifact.
+pub struct MetadataCache {
+    directory: PathBuf,
+    policy: CachePolicy,
+    /// Whether downloaded metadata is kept out of the cache.
+    read_only: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    /// The time the metadata was downloaded, in seconds since the Unix epoch.
+    fetched_at: u64,
+    metadata: String,
+}
+
+impl MetadataCache {
+    pub fn new(directory: impl Into<PathBuf>, policy: CachePolicy) -> Self {
+        Self {
+            directory: directory.into(),
+            policy,
+            read_only: false,
+        }
+    }
+
+    /// Uses the cached metadata without writing downloaded metadata to the cache.
+    pub fn read_only(self) -> Self {
+        Self { read_only: true, ..self }
+    }
+
+    /// The default cache directory in the user's cache folder, if there is one.
+    pub fn default_directory() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("architectury-template-generator").join("maven"))
+    }
+
+    fn entry_path(&self, repository_url: &str, library: &MavenLibrary) -> PathBuf {
+        let repository = repository_url
+            .split_once("://")
+            .map_or(repository_url, |(_, rest)| rest)
+            .trim_end_matches('/')
+            .replace(|c: char| !c.is_ascii_alphanumeric() && c != '.' && c != '-', "_");
+        self.directory
+            .join(repository)
+            .join(library.group())
+            .join(format!("{}.json", library.name()))
+    }
+
+    async fn read(&self, path: &PathBuf) -> Option<CacheEntry> {
+        let json = tokio::fs::read_to_string(path).await.ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    /// Writes an entry to a temporary file that is renamed into place,
+    /// so concurrent readers and writers never see a partially written entry.
+    async fn write(&self, path: &PathBuf, entry: &CacheEntry) -> Result<()> {
+        static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let temp_path = path.with_extension(format!(
+            "{}.{}.tmp",
+            std::process::id(),
+            TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed),
+        ));
+        tokio::fs::write(&temp_path, serde_json::to_string(entry)?).await?;
+        if let Err(error) = tokio::fs::rename(&temp_path, path).await {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+            return Err(error.into());
+        }
+        Ok(())
+    }
+
+    /// Returns the cached metadata of the library if it can be used according to the policy,
+    /// or downloads it and updates the cache otherwise.
+    pub(super) async fn get_or_download<F, Fut>(
+        &self,
+        repository_url: &str,
+        library: &MavenLibrary,
+        download: F,
+    ) -> Result<String>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<String>>,
+    {
+        let path = self.entry_path(repository_url, library);
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .wrap_err("System clock is set before the Unix epoch")?
+            .as_secs();
+
+        match self.policy {
+            CachePolicy::MaxAge(max_age) => {
+                if let Some(entry) = self.read(&path).await {
+                    if now.saturating_sub(entry.fetched_at) <= max_age.as_secs() {
+                        return Ok(entry.metadata);
+                    }
+                }
+            }
+            CachePolicy::CacheOnly => {
+                return self.read(&path)
+                    .await
+                    .map(|entry| entry.metadata)
+                    .ok_or_else(|| err!("Maven metadata for {} is not cached", library));
+            }
+            CachePolicy::Refresh => {}
+        }
+
+        let metadata = download().await?;
+        if self.read_only {
+            return Ok(metadata);
+        }
+        let entry = CacheEntry { fetched_at: now, metadata };
+
+        // Failing to update the cache shouldn't prevent using the downloaded metadata.
+        if let Err(error) = self.write(&path, &entry).await {
+            eprintln!("Could not cache Maven metadata for {}: {}", library, error);
+        }
+
+        Ok(entry.metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn fresh_entries_are_reused() {
+        let library = MavenLibrary::fabric_loader();
+        let directory = tempfile::tempdir().unwrap();
+        let cache = MetadataCache::new(directory.path(), CachePolicy::MaxAge(Duration::from_secs(3600)));
+        let first = cache
+            .get_or_download("https://maven.example", &library, || async { Ok("first".to_owned()) })
+            .await
+            .unwrap();
+        let second = cache
+            .get_or_download("https://maven.example", &library, || async { Ok("second".to_owned()) })
+            .await
+            .unwrap();
+        assert_eq!(first, "first");
+        assert_eq!(second, "first");
+
+        let refreshed = MetadataCache::new(cache.directory.clone(), CachePolicy::Refresh)
+            .get_or_download("https://maven.example", &library, || async { Ok("third".to_owned()) })
+            .await
+            .unwrap();
+        assert_eq!(refreshed, "third");
+    }
+
+    #[tokio::test]
+    async fn cache_only_fails_on_missing_entries() {
+        let library = MavenLibrary::fabric_loader();
+        let directory = tempfile::tempdir().unwrap();
+        let cache = MetadataCache::new(directory.path(), CachePolicy::CacheOnly);
+        let result = cache
+            .get_or_download("https://maven.example", &library, || async { Ok("downloaded".to_owned()) })
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn read_only_caches_are_not_written() {
+        let library = MavenLibrary::fabric_loader();
+        let directory = tempfile::tempdir().unwrap();
+        let cache = MetadataCache::new(directory.path(), CachePolicy::Refresh).read_only();
+        let metadata = cache
+            .get_or_download("https://maven.example", &library, || async { Ok("downloaded".to_owned()) })
+            .await
+            .unwrap();
+        assert_eq!(metadata, "downloaded");
+        assert_eq!(std::fs::read_dir(directory.path()).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn concurrent_writes_leave_a_complete_entry() {
+        let library = MavenLibrary::fabric_loader();
+        let directory = tempfile::tempdir().unwrap();
+        let cache = MetadataCache::new(directory.path(), CachePolicy::Refresh);
+        let writes = (0..16u8).map(|i| {
+            let metadata = char::from(b'a' + i).to_string().repeat(10_000);
+            let cache = &cache;
+            let library = &library;
+            async move {
+                cache.get_or_download("https://maven.example", library, || async { Ok(metadata) }).await
+            }
+        });
+        futures::future::try_join_all(writes).await.unwrap();
+
+        let path = cache.entry_path("https://maven.example", &library);
+        let entry = cache.read(&path).await.unwrap();
+        assert_eq!(entry.metadata.len(), 10_000);
+        let files = std::fs::read_dir(path.parent().unwrap()).unwrap().count();
+        assert_eq!(files, 1, "temporary files were left behind");
+    }
+}
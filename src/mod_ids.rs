@@ -8,7 +8,7 @@ const MIN_LENGTH: usize = 2;
 const MAX_LENGTH: usize = 64;
 
 fn is_valid_id_start(c: char) -> bool {
-    matches!(c, 'a'..='z')
+    c.is_ascii_lowercase()
 }
 
 fn is_valid_in_id(c: char) -> bool {
@@ -8,7 +8,7 @@ use std::fmt::Display;
 #[macro_export]
 macro_rules! err {
     ($($arg:tt)*) => {
-        crate::result::Error::from(format!($($arg)*))
+        $crate::result::Error::from(format!($($arg)*))
     };
 }
 
@@ -18,6 +18,11 @@ pub type Result<T, E = Error> = std::result::Result<T, E>;
 pub struct Error(Box<dyn StdError>);
 
 impl Error {
+    /// Returns the underlying error if it has the type `E`.
+    pub fn downcast_ref<E: StdError + 'static>(&self) -> Option<&E> {
+        self.0.downcast_ref()
+    }
+
     #[cfg(target_family = "wasm")]
     pub fn from_js(value: wasm_bindgen::JsValue) -> Self {
         format!("{:?}", value).into()
@@ -5,13 +5,13 @@
 pub trait Tap {
     fn tap<F>(self, f: F) -> Self
     where
-        F: FnOnce(&Self) -> ();
+        F: FnOnce(&Self);
 }
 
 impl<T> Tap for T {
     fn tap<F>(self, f: F) -> Self
     where
-        F: FnOnce(&Self) -> (),
+        F: FnOnce(&Self),
     {
         f(&self);
         self
@@ -0,0 +1,172 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use super::Context;
+
+/// A boolean expression over context flags and variables.
+///
+/// The grammar, from lowest to highest precedence:
+/// ```text
+/// or-expr  = and-expr ("or" and-expr)*
+/// and-expr = not-expr ("and" not-expr)*
+/// not-expr = "not" not-expr | primary
+/// primary  = FLAG | "(" or-expr ")"
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Condition {
+    /// True if the context has a flag or variable with this name.
+    Defined(String),
+    Not(Box<Condition>),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+}
+
+impl Condition {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let tokens = tokenize(input);
+        if tokens.is_empty() {
+            return Err("Missing condition".to_owned());
+        }
+
+        let mut parser = Parser { tokens, position: 0 };
+        let condition = parser.parse_or()?;
+        match parser.peek() {
+            None => Ok(condition),
+            Some(token) => Err(format!("Unexpected '{}' in condition", token)),
+        }
+    }
+
+    pub fn evaluate(&self, context: &Context) -> bool {
+        match self {
+            Self::Defined(name) => context.has(name),
+            Self::Not(inner) => !inner.evaluate(context),
+            Self::And(left, right) => left.evaluate(context) && right.evaluate(context),
+            Self::Or(left, right) => left.evaluate(context) || right.evaluate(context),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start: Option<usize> = None;
+
+    for (i, c) in input.char_indices() {
+        if c.is_whitespace() || c == '(' || c == ')' {
+            if let Some(s) = start.take() {
+                tokens.push(&input[s..i]);
+            }
+            if !c.is_whitespace() {
+                tokens.push(&input[i..i + 1]);
+            }
+        } else if start.is_none() {
+            start = Some(i);
+        }
+    }
+
+    if let Some(s) = start {
+        tokens.push(&input[s..]);
+    }
+
+    tokens
+}
+
+struct Parser<'a> {
+    tokens: Vec<&'a str>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<&'a str> {
+        let token = self.peek();
+        self.position += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<Condition, String> {
+        let mut condition = self.parse_and()?;
+        while self.peek() == Some("or") {
+            self.next();
+            condition = Condition::Or(Box::new(condition), Box::new(self.parse_and()?));
+        }
+        Ok(condition)
+    }
+
+    fn parse_and(&mut self) -> Result<Condition, String> {
+        let mut condition = self.parse_not()?;
+        while self.peek() == Some("and") {
+            self.next();
+            condition = Condition::And(Box::new(condition), Box::new(self.parse_not()?));
+        }
+        Ok(condition)
+    }
+
+    fn parse_not(&mut self) -> Result<Condition, String> {
+        if self.peek() == Some("not") {
+            self.next();
+            return Ok(Condition::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Condition, String> {
+        match self.next() {
+            Some("(") => {
+                let condition = self.parse_or()?;
+                match self.next() {
+                    Some(")") => Ok(condition),
+                    Some(token) => Err(format!("Expected ')' but found '{}' in condition", token)),
+                    None => Err("Missing ')' in condition".to_owned()),
+                }
+            }
+            Some(token @ (")" | "and" | "or" | "not")) => {
+                Err(format!("Expected a flag but found '{}' in condition", token))
+            }
+            Some(token) => Ok(Condition::Defined(token.to_owned())),
+            None => Err("Unexpected end of condition".to_owned()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defined(name: &str) -> Box<Condition> {
+        Box::new(Condition::Defined(name.to_owned()))
+    }
+
+    #[test]
+    fn precedence() {
+        let condition = Condition::parse("not a or b and (c or d)").unwrap();
+        assert_eq!(
+            condition,
+            Condition::Or(
+                Box::new(Condition::Not(defined("a"))),
+                Box::new(Condition::And(defined("b"), Box::new(Condition::Or(defined("c"), defined("d"))))),
+            )
+        );
+    }
+
+    #[test]
+    fn invalid_conditions() {
+        assert!(Condition::parse("").is_err());
+        assert!(Condition::parse("a and").is_err());
+        assert!(Condition::parse("(a or b").is_err());
+        assert!(Condition::parse("a b").is_err());
+    }
+
+    #[test]
+    fn evaluate() {
+        let mut context = Context::new();
+        context.define("fabric");
+        context.put("NEOFORGE_VERSION", "21.1.90");
+        assert!(Condition::parse("fabric and NEOFORGE_VERSION").unwrap().evaluate(&context));
+        assert!(Condition::parse("quilt or not forge").unwrap().evaluate(&context));
+        assert!(!Condition::parse("not (fabric or quilt)").unwrap().evaluate(&context));
+    }
+}
@@ -0,0 +1,90 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+/// The names of the built-in filters that can be used as `%KEY|filter%`.
+pub const FILTERS: [&str; 6] = ["pascal", "camel", "snake", "kebab", "upper", "lower"];
+
+pub fn is_filter(name: &str) -> bool {
+    FILTERS.contains(&name)
+}
+
+/// Applies a built-in filter to a value, or returns `None` if there is no filter with the name.
+pub fn apply_filter(name: &str, value: &str) -> Option<String> {
+    Some(match name {
+        "pascal" => words(value).iter().map(|word| capitalize(word)).collect(),
+        "camel" => words(value)
+            .iter()
+            .enumerate()
+            .map(|(i, word)| if i == 0 { word.to_lowercase() } else { capitalize(word) })
+            .collect(),
+        "snake" => join_lowercase(value, "_"),
+        "kebab" => join_lowercase(value, "-"),
+        "upper" => value.to_uppercase(),
+        "lower" => value.to_lowercase(),
+        _ => return None,
+    })
+}
+
+/// Splits text into words at non-alphanumeric characters and at case changes,
+/// so that `My coolMod` and `HTTPServer` become `My cool Mod` and `HTTP Server`.
+fn words(text: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+
+    for part in text.split(|c: char| !c.is_alphanumeric()).filter(|part| !part.is_empty()) {
+        let chars: Vec<(usize, char)> = part.char_indices().collect();
+        let mut start = 0;
+
+        for i in 1..chars.len() {
+            let (index, c) = chars[i];
+            let previous = chars[i - 1].1;
+            let next = chars.get(i + 1).map(|(_, c)| *c);
+            let is_boundary = c.is_uppercase()
+                && (previous.is_lowercase()
+                    || previous.is_numeric()
+                    || (previous.is_uppercase() && next.is_some_and(char::is_lowercase)));
+
+            if is_boundary {
+                words.push(&part[start..index]);
+                start = index;
+            }
+        }
+
+        words.push(&part[start..]);
+    }
+
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn join_lowercase(text: &str, separator: &str) -> String {
+    words(text)
+        .iter()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn case_conversion() {
+        let apply = |name: &str, value: &str| apply_filter(name, value).unwrap();
+        assert_eq!(apply("pascal", "my cool-mod"), "MyCoolMod");
+        assert_eq!(apply("pascal", "HTTPServer utils 2"), "HTTPServerUtils2");
+        assert_eq!(apply("camel", "My Cool Mod"), "myCoolMod");
+        assert_eq!(apply("snake", "My coolMod"), "my_cool_mod");
+        assert_eq!(apply("kebab", "My \"Great\" Mod"), "my-great-mod");
+        assert_eq!(apply("upper", "my_mod"), "MY_MOD");
+        assert!(apply_filter("title", "My Mod").is_none());
+    }
+}
@@ -3,22 +3,111 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+mod condition;
+pub mod filters;
+pub use condition::Condition;
 
 pub enum TemplatePart {
-    Line(String),
+    Line {
+        text: String,
+        line_number: usize,
+    },
     Conditional {
-        condition: String,
+        condition: Condition,
         if_true: Box<Vec<TemplatePart>>,
         if_false: Box<Vec<TemplatePart>>,
     },
+    /// A partial included with `include NAME KEY=value...`.
+    /// The arguments are added to the context while rendering the partial.
+    Include {
+        name: String,
+        arguments: Vec<(String, String)>,
+        parts: Vec<TemplatePart>,
+    },
+}
+
+/// The header of a template, written as `//%! command argument` or `#%! command argument`
+/// lines at the start of the file.
+#[derive(Default)]
+pub struct FrontMatter {
+    /// The condition for emitting the file. Files without a condition are always emitted.
+    pub when: Option<Condition>,
+}
+
+impl FrontMatter {
+    pub fn is_included(&self, context: &Context) -> bool {
+        self.when.as_ref().is_none_or(|condition| condition.evaluate(context))
+    }
+}
+
+fn front_matter_command(line: &str) -> Option<&str> {
+    line.strip_prefix("//%!").or_else(|| line.strip_prefix("#%!"))
+}
+
+pub fn read_front_matter<S: AsRef<str>>(input: S) -> Result<FrontMatter, String> {
+    let mut front_matter = FrontMatter::default();
+    for (line_index, line) in input.as_ref().lines().enumerate() {
+        let line_number = line_index + 1;
+        let Some(command) = front_matter_command(line) else {
+            break;
+        };
+
+        let command = command.trim();
+        let (name, argument) = command.split_once(char::is_whitespace).unwrap_or((command, ""));
+        match name {
+            "when" => {
+                if front_matter.when.is_some() {
+                    return Err(format!("Duplicate when condition on line {}", line_number));
+                }
+                let condition = Condition::parse(argument)
+                    .map_err(|err| format!("{} on line {}", err, line_number))?;
+                front_matter.when = Some(condition);
+            }
+            _ => {
+                return Err(format!(
+                    "Unknown front matter command '{}' on line {}",
+                    name,
+                    line_number
+                ))
+            }
+        }
+    }
+
+    Ok(front_matter)
+}
+
+/// Shared template fragments that can be included in other templates, by file name.
+#[derive(Default)]
+pub struct Partials {
+    templates: HashMap<String, String>,
+}
+
+impl Partials {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert<N: Into<String>, T: Into<String>>(&mut self, name: N, template: T) {
+        self.templates.insert(name.into(), template.into());
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.templates.get(name).map(String::as_str)
+    }
 }
 
 #[derive(Default)]
 struct Frame {
     true_parts: Vec<TemplatePart>,
     false_parts: Vec<TemplatePart>,
-    condition: Option<String>,
+    condition: Option<Condition>,
     in_else: bool,
+    /// The line number of the `if` or `elif` command that opened this frame.
+    line_number: usize,
+    /// Whether this frame was opened by `elif`, so that it's closed by the same `end` as its parent.
+    is_elif: bool,
 }
 
 impl Frame {
@@ -31,65 +120,166 @@ impl Frame {
     }
 }
 
+fn close_frame(frames: &mut Vec<Frame>) {
+    let frame = frames.pop().unwrap();
+    frames
+        .last_mut()
+        .unwrap()
+        .current_parts()
+        .push(TemplatePart::Conditional {
+            condition: frame.condition.unwrap(),
+            if_true: Box::new(frame.true_parts),
+            if_false: Box::new(frame.false_parts),
+        })
+}
+
 pub fn read_template<S: AsRef<str>>(input: S) -> Result<Vec<TemplatePart>, String> {
-    let input = input.as_ref();
+    read_template_with_partials(input, &Partials::new())
+}
+
+/// Reads a template like [`read_template`], resolving its `include` commands from `partials`.
+pub fn read_template_with_partials<S: AsRef<str>>(input: S, partials: &Partials) -> Result<Vec<TemplatePart>, String> {
+    read_template_inner(input.as_ref(), partials, &mut Vec::new())
+}
+
+/// Parses a template. `include_stack` contains the names of the partials being read, to detect include cycles.
+fn read_template_inner(input: &str, partials: &Partials, include_stack: &mut Vec<String>) -> Result<Vec<TemplatePart>, String> {
     let mut frames = vec![Frame::default()];
-    for (line_number, line) in input.lines().enumerate() {
-        if line.starts_with("//%") || line.starts_with("#%") {
-            let parts: Vec<&str> = line.split_whitespace().skip(1).collect();
-            match parts[0] {
-                "if" => {
-                    let mut frame = Frame::default();
-                    frame.condition = Some(parts[1].to_owned());
-                    frames.push(frame);
-                }
-                "else" => {
-                    let frame = frames.last_mut().unwrap();
-                    if frame.condition.is_none() {
-                        return Err("Cannot add else block at top level".to_owned());
-                    }
-                    frame.in_else = true;
-                }
-                "end" => {
-                    if frames.len() <= 1 {
-                        return Err("Cannot end if block at top level".to_owned());
-                    }
-                    let frame = frames.pop().unwrap();
-                    frames
-                        .last_mut()
-                        .unwrap()
-                        .current_parts()
-                        .push(TemplatePart::Conditional {
-                            condition: frame.condition.unwrap(),
-                            if_true: Box::new(frame.true_parts),
-                            if_false: Box::new(frame.false_parts),
-                        })
-                }
-                _ => {
-                    return Err(format!(
-                        "Unknown template command {} on line {}",
-                        parts[1],
-                        line_number + 1
-                    ))
-                }
+    let mut in_front_matter = true;
+    for (line_index, line) in input.lines().enumerate() {
+        let line_number = line_index + 1;
+        if front_matter_command(line).is_some() {
+            if !in_front_matter {
+                return Err(format!("Front matter must be at the start of the template on line {}", line_number));
             }
-        } else {
+            continue;
+        }
+        in_front_matter = false;
+
+        let command = line.strip_prefix("//%").or_else(|| line.strip_prefix("#%"));
+        let Some(command) = command else {
+            check_filters(line).map_err(|err| format!("{} on line {}", err, line_number))?;
             frames
                 .last_mut()
                 .unwrap()
                 .current_parts()
-                .push(TemplatePart::Line(line.to_owned()));
+                .push(TemplatePart::Line {
+                    text: line.to_owned(),
+                    line_number,
+                });
+            continue;
+        };
+
+        let command = command.trim();
+        let (name, argument) = command.split_once(char::is_whitespace).unwrap_or((command, ""));
+        let parse_condition = || {
+            Condition::parse(argument).map_err(|err| format!("{} on line {}", err, line_number))
+        };
+        match name {
+            "if" => {
+                frames.push(Frame {
+                    condition: Some(parse_condition()?),
+                    line_number,
+                    ..Default::default()
+                });
+            }
+            "elif" | "else" => {
+                let frame = frames.last_mut().unwrap();
+                if frame.condition.is_none() {
+                    return Err(format!("Cannot add {} block at top level on line {}", name, line_number));
+                }
+                if frame.in_else {
+                    return Err(format!("Cannot add {} block after else on line {}", name, line_number));
+                }
+                frame.in_else = true;
+
+                if name == "elif" {
+                    frames.push(Frame {
+                        condition: Some(parse_condition()?),
+                        line_number,
+                        is_elif: true,
+                        ..Default::default()
+                    });
+                } else if !argument.is_empty() {
+                    return Err(format!("Unexpected '{}' after else on line {}", argument, line_number));
+                }
+            }
+            "include" => {
+                let part = read_include(argument, partials, include_stack)
+                    .map_err(|err| format!("{} on line {}", err, line_number))?;
+                frames.last_mut().unwrap().current_parts().push(part);
+            }
+            "end" => {
+                if frames.len() <= 1 {
+                    return Err(format!("Cannot end if block at top level on line {}", line_number));
+                }
+                // Close the frames of the elif blocks before the frame of the if block.
+                while frames.last().unwrap().is_elif {
+                    close_frame(&mut frames);
+                }
+                close_frame(&mut frames);
+            }
+            _ => {
+                return Err(format!(
+                    "Unknown template command '{}' on line {}",
+                    name,
+                    line_number
+                ))
+            }
         }
     }
 
+    if frames.len() > 1 {
+        let unclosed = frames.iter().rev().find(|frame| !frame.is_elif).unwrap();
+        return Err(format!("Missing end for if block on line {}", unclosed.line_number));
+    }
+
     Ok(frames.pop().unwrap().true_parts)
 }
 
+fn read_include(argument: &str, partials: &Partials, include_stack: &mut Vec<String>) -> Result<TemplatePart, String> {
+    let mut words = argument.split_whitespace();
+    let name = words.next().ok_or("Missing partial name")?;
+    let arguments = words
+        .map(|word| {
+            word.split_once('=')
+                .filter(|(key, _)| Placeholder::parse(key, key).is_some_and(|p| p.filters.is_empty()))
+                .map(|(key, value)| (key.to_owned(), value.to_owned()))
+                .ok_or_else(|| format!("Invalid include argument '{}', expected KEY=value", word))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if include_stack.iter().any(|included| included == name) {
+        return Err(format!("Include cycle {} -> {}", include_stack.join(" -> "), name));
+    }
+    let template = partials.get(name).ok_or_else(|| format!("Unknown partial '{}'", name))?;
+    if front_matter_command(template.lines().next().unwrap_or_default()).is_some() {
+        return Err(format!("Partial {} cannot have front matter", name));
+    }
+
+    include_stack.push(name.to_owned());
+    let parts = read_template_inner(template, partials, include_stack);
+    include_stack.pop();
+
+    Ok(TemplatePart::Include {
+        name: name.to_owned(),
+        arguments,
+        parts: parts.map_err(|err| format!("{} in partial {}", err, name))?,
+    })
+}
+
+#[derive(Clone)]
 pub struct Context {
     variables: HashMap<String, String>,
     flags: HashSet<String>,
 }
 
+impl Default for Context {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Context {
     pub fn new() -> Self {
         Self {
@@ -131,37 +321,419 @@ impl Context {
     }
 }
 
-pub fn apply_variables(context: &Context, text: &str, use_delimiters: bool) -> String {
-    let mut text = String::from(text);
+/// A piece of template text.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TextToken<'a> {
+    Literal(&'a str),
+    Placeholder(Placeholder<'a>),
+}
 
-    for (key, value) in context.iter() {
-        let to_replace = if use_delimiters {
-            format!("%{}%", key)
-        } else {
-            key.to_owned()
+/// A `%KEY%` or `%KEY|filter|...%` placeholder.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Placeholder<'a> {
+    pub key: &'a str,
+    /// The filters applied to the value, in order.
+    pub filters: Vec<&'a str>,
+    /// The placeholder as written in the template, including the delimiters.
+    pub source: &'a str,
+}
+
+impl<'a> Placeholder<'a> {
+    /// Parses the text between the delimiters of a placeholder.
+    /// Keys consist of uppercase letters, digits and underscores, and start with a letter.
+    /// Filter names consist of lowercase letters, digits and underscores.
+    fn parse(content: &'a str, source: &'a str) -> Option<Self> {
+        let mut parts = content.split('|');
+        let key = parts.next()?;
+        let is_key = key.starts_with(|c: char| c.is_ascii_uppercase())
+            && key.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+        if !is_key {
+            return None;
+        }
+
+        let filters: Vec<&str> = parts.collect();
+        let are_filters = filters.iter().all(|filter| {
+            filter.starts_with(|c: char| c.is_ascii_lowercase())
+                && filter.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        });
+        are_filters.then_some(Self { key, filters, source })
+    }
+
+    /// Applies the filters to the value, or returns `None` if a filter doesn't exist.
+    pub fn apply_filters(&self, value: &str) -> Option<String> {
+        let mut value = value.to_owned();
+        for filter in &self.filters {
+            value = filters::apply_filter(filter, &value)?;
+        }
+        Some(value)
+    }
+}
+
+/// Splits text into literals and `%KEY%` placeholders in a single pass.
+pub fn tokenize(text: &str) -> Vec<TextToken<'_>> {
+    let mut tokens = Vec::new();
+    let mut literal_start = 0;
+    let mut search_start = 0;
+
+    while let Some(offset) = text[search_start..].find('%') {
+        let start = search_start + offset;
+        let Some(length) = text[start + 1..].find('%') else {
+            break;
         };
-        text = text.replace(&to_replace, value);
+        let end = start + 1 + length;
+
+        if let Some(placeholder) = Placeholder::parse(&text[start + 1..end], &text[start..=end]) {
+            if literal_start < start {
+                tokens.push(TextToken::Literal(&text[literal_start..start]));
+            }
+            tokens.push(TextToken::Placeholder(placeholder));
+            literal_start = end + 1;
+            search_start = end + 1;
+        } else {
+            // The closing % might start the next placeholder.
+            search_start = end;
+        }
+    }
+
+    if literal_start < text.len() {
+        tokens.push(TextToken::Literal(&text[literal_start..]));
+    }
+
+    tokens
+}
+
+fn placeholders(text: &str) -> impl Iterator<Item = Placeholder<'_>> {
+    tokenize(text).into_iter().filter_map(|token| match token {
+        TextToken::Placeholder(placeholder) => Some(placeholder),
+        TextToken::Literal(_) => None,
+    })
+}
+
+/// Finds the keys of all `%KEY%` placeholders in the text.
+pub fn find_placeholders(text: &str) -> Vec<&str> {
+    placeholders(text).map(|placeholder| placeholder.key).collect()
+}
+
+/// Checks that all filters used in the text exist.
+fn check_filters(text: &str) -> Result<(), String> {
+    for placeholder in placeholders(text) {
+        if let Some(filter) = placeholder.filters.iter().find(|filter| !filters::is_filter(filter)) {
+            return Err(format!(
+                "Unknown filter '{}' in {}; expected one of {}",
+                filter,
+                placeholder.source,
+                filters::FILTERS.join(", ")
+            ));
+        }
     }
 
-    text
+    Ok(())
 }
 
+/// Substitutes the `%KEY%` placeholders in the text. Unknown placeholders are kept as is,
+/// and substituted values are never substituted again.
+pub fn apply_variables(context: &Context, text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+
+    for token in tokenize(text) {
+        match token {
+            TextToken::Literal(literal) => output.push_str(literal),
+            TextToken::Placeholder(placeholder) => {
+                let value = context.variables.get(placeholder.key)
+                    .and_then(|value| placeholder.apply_filters(value));
+                output.push_str(value.as_deref().unwrap_or(placeholder.source));
+            }
+        }
+    }
+
+    output
+}
+
+/// Substitutes the `%KEY%` placeholders in an output path.
+/// Unlike in file contents, unknown placeholders are always an error.
+pub fn apply_path_variables(context: &Context, path: &str) -> Result<String, String> {
+    check_filters(path)?;
+    let unknown: Vec<&str> = find_placeholders(path)
+        .into_iter()
+        .filter(|key| !context.variables.contains_key(*key))
+        .collect();
+    if !unknown.is_empty() {
+        return Err(format!("Unknown placeholders %{}% in path {}", unknown.join("%, %"), path));
+    }
+
+    Ok(apply_variables(context, path))
+}
+
+/// Controls what happens to `%KEY%` placeholders that have no value in the context.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum RenderMode {
+    /// Leave unresolved placeholders in the output.
+    #[default]
+    Lenient,
+    /// Fail with [`UnresolvedPlaceholders`] if there are unresolved placeholders.
+    Strict,
+}
+
+/// A `%KEY%` placeholder that has no value in the context.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct UnresolvedPlaceholder {
+    pub key: String,
+    pub file: String,
+    pub line_number: usize,
+}
+
+/// The error returned when rendering in [`RenderMode::Strict`] finds unresolved placeholders.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct UnresolvedPlaceholders(pub Vec<UnresolvedPlaceholder>);
+
+impl Display for UnresolvedPlaceholders {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unresolved template placeholders:")?;
+        for placeholder in &self.0 {
+            write!(f, "\n  %{}% in {}:{}", placeholder.key, placeholder.file, placeholder.line_number)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for UnresolvedPlaceholders {}
+
 pub fn apply_template(context: &Context, parts: Vec<TemplatePart>) -> Vec<String> {
     let mut output: Vec<String> = vec![];
+    apply_template_parts(context, parts, &mut output, &mut |_, _, _, _| {});
+    output
+}
+
+/// Renders a template like [`apply_template`], but fails in [`RenderMode::Strict`]
+/// if any placeholders are unresolved. `file` is only used for the error,
+/// together with the name of the partial for placeholders in included partials.
+pub fn apply_template_with_mode(
+    context: &Context,
+    parts: Vec<TemplatePart>,
+    mode: RenderMode,
+    file: &str,
+) -> Result<Vec<String>, UnresolvedPlaceholders> {
+    let mut output: Vec<String> = vec![];
+    let mut unresolved: Vec<UnresolvedPlaceholder> = vec![];
+    apply_template_parts(context, parts, &mut output, &mut |context, text, line_number, partial| {
+        if mode == RenderMode::Strict {
+            for key in find_placeholders(text) {
+                if !context.variables.contains_key(key) {
+                    unresolved.push(UnresolvedPlaceholder {
+                        key: key.to_owned(),
+                        file: match partial {
+                            Some(partial) => format!("{} (included in {})", partial, file),
+                            None => file.to_owned(),
+                        },
+                        line_number,
+                    });
+                }
+            }
+        }
+    });
+
+    if unresolved.is_empty() {
+        Ok(output)
+    } else {
+        Err(UnresolvedPlaceholders(unresolved))
+    }
+}
+
+fn apply_template_parts<F>(context: &Context, parts: Vec<TemplatePart>, output: &mut Vec<String>, inspect_line: &mut F)
+where
+    F: FnMut(&Context, &str, usize, Option<&str>),
+{
+    apply_template_parts_in(context, parts, output, inspect_line, None)
+}
 
+/// Renders template parts. `partial` is the name of the partial that the parts are from, if any.
+fn apply_template_parts_in<F>(
+    context: &Context,
+    parts: Vec<TemplatePart>,
+    output: &mut Vec<String>,
+    inspect_line: &mut F,
+    partial: Option<&str>,
+) where
+    F: FnMut(&Context, &str, usize, Option<&str>),
+{
     for part in parts {
         match part {
-            TemplatePart::Line(text) => output.push(apply_variables(context, text.as_str(), true)),
+            TemplatePart::Line { text, line_number } => {
+                inspect_line(context, &text, line_number, partial);
+                output.push(apply_variables(context, text.as_str()));
+            }
             TemplatePart::Conditional { condition, if_true, if_false } => {
-                let parts = if context.has(&condition) {
+                let parts = if condition.evaluate(context) {
                     if_true
                 } else {
                     if_false
                 };
-                output.append(&mut apply_template(context, *parts));
+                apply_template_parts_in(context, *parts, output, inspect_line, partial);
+            }
+            TemplatePart::Include { name, arguments, parts } => {
+                let mut partial_context = context.clone();
+                for (key, value) in arguments {
+                    partial_context.put(key, apply_variables(context, &value));
+                }
+                apply_template_parts_in(&partial_context, parts, output, inspect_line, Some(&name));
             }
         }
     }
+}
 
-    output
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(template: &str, flags: &[&str]) -> Result<Vec<String>, String> {
+        let mut context = Context::new();
+        for flag in flags {
+            context.define(flag);
+        }
+        Ok(apply_template(&context, read_template(template)?))
+    }
+
+    #[test]
+    fn elif_chains() {
+        let template = "//% if fabric and quilt\nfabric-like\n//% elif fabric\nfabric\n//% elif not forge\nnone\n//% else\nforge\n//% end\nafter";
+        assert_eq!(render(template, &["fabric", "quilt"]).unwrap(), ["fabric-like", "after"]);
+        assert_eq!(render(template, &["fabric"]).unwrap(), ["fabric", "after"]);
+        assert_eq!(render(template, &[]).unwrap(), ["none", "after"]);
+        assert_eq!(render(template, &["forge"]).unwrap(), ["forge", "after"]);
+    }
+
+    #[test]
+    fn errors_have_line_numbers() {
+        let error = |template: &str| read_template(template).err().unwrap();
+        assert_eq!(error("a\n#% if fabric and\n#% end"), "Unexpected end of condition on line 2");
+        assert_eq!(error("#% if a\n#% else\n#% elif b\n#% end"), "Cannot add elif block after else on line 3");
+        assert_eq!(error("a\n#% end"), "Cannot end if block at top level on line 2");
+        assert_eq!(error("#% if a\n#% elif b\n#% if c\nc"), "Missing end for if block on line 3");
+        assert_eq!(error("#% if a\n#% elif b"), "Missing end for if block on line 1");
+        assert_eq!(error("#% iff a"), "Unknown template command 'iff' on line 1");
+    }
+
+    #[test]
+    fn front_matter() {
+        let template = "#%! when neoforge and not mc_1_20_4\nmodLoader = \"javafml\"\n#% if forge\n#% end";
+        let front_matter = read_front_matter(template).unwrap();
+        let mut context = Context::new();
+        context.define("neoforge");
+        assert!(front_matter.is_included(&context));
+        context.define("mc_1_20_4");
+        assert!(!front_matter.is_included(&context));
+        assert!(read_front_matter("a = 1").unwrap().is_included(&context));
+        assert_eq!(apply_template(&context, read_template(template).unwrap()), ["modLoader = \"javafml\""]);
+
+        assert_eq!(read_front_matter("//%! when a\n//%! when b").err().unwrap(), "Duplicate when condition on line 2");
+        assert_eq!(read_front_matter("//%! unless a").err().unwrap(), "Unknown front matter command 'unless' on line 1");
+        assert_eq!(
+            read_template("a\n//%! when b").err().unwrap(),
+            "Front matter must be at the start of the template on line 2"
+        );
+    }
+
+    #[test]
+    fn substitution_does_not_cascade() {
+        let mut context = Context::new();
+        context.put("MOD_NAME", "%MOD_ID% Mod");
+        context.put("MOD_ID", "MOD_NAME");
+        assert_eq!(apply_variables(&context, "%MOD_NAME% (%MOD_ID%) 50%"), "%MOD_ID% Mod (MOD_NAME) 50%");
+    }
+
+    #[test]
+    fn filters() {
+        let mut context = Context::new();
+        context.put("MOD_NAME", "My Cool Mod");
+        let line = "public class %MOD_NAME|pascal%Fabric { // %MOD_NAME|snake|upper% %MOD_NAME|nope%";
+        assert_eq!(apply_variables(&context, line), "public class MyCoolModFabric { // MY_COOL_MOD %MOD_NAME|nope%");
+        assert_eq!(
+            read_template("a\n%MOD_NAME|title%").err().unwrap(),
+            "Unknown filter 'title' in %MOD_NAME|title%; expected one of pascal, camel, snake, kebab, upper, lower on line 2"
+        );
+    }
+
+    #[test]
+    fn path_placeholders() {
+        let mut context = Context::new();
+        context.put("PACKAGE_DIR", "com/example");
+        context.put("MOD_ID", "my_mod");
+        let path = apply_path_variables(&context, "src/%PACKAGE_DIR%/MOD_ID/%MOD_ID%.mixins.json");
+        assert_eq!(path.unwrap(), "src/com/example/MOD_ID/my_mod.mixins.json");
+        assert!(apply_path_variables(&context, "src/%PACKAGE_NAME%/Main.java").is_err());
+    }
+
+    #[test]
+    fn find_placeholder_keys() {
+        assert_eq!(find_placeholders("%A% 100% %B_2% %not% %%C%"), ["A", "B_2", "C"]);
+    }
+
+    #[test]
+    fn strict_mode_reports_unresolved_placeholders() {
+        let mut context = Context::new();
+        context.put("MOD_ID", "my_mod");
+        let template = "mod_id = %MOD_ID%\n#% if forge\nforge = %FORGE_VERSION%\n#% end\nloader = %LOADER% %FORGE_VERSION%";
+        let lenient = apply_template_with_mode(&context, read_template(template).unwrap(), RenderMode::Lenient, "a");
+        assert_eq!(lenient.unwrap(), ["mod_id = my_mod", "loader = %LOADER% %FORGE_VERSION%"]);
+
+        let strict = apply_template_with_mode(&context, read_template(template).unwrap(), RenderMode::Strict, "a");
+        let keys: Vec<_> = strict.unwrap_err().0.into_iter().map(|p| (p.key, p.line_number)).collect();
+        assert_eq!(keys, [("LOADER".to_owned(), 5), ("FORGE_VERSION".to_owned(), 5)]);
+    }
+
+    #[test]
+    fn includes() {
+        let mut partials = Partials::new();
+        partials.insert("shadow.gradle", "//% if fabric\ndevelopment%PLATFORM%\n//% end\n%MOD_ID% %MISSING%");
+        partials.insert("a", "//% include b");
+        partials.insert("b", "//% include a");
+        let template = "first\n//% include shadow.gradle PLATFORM=%MOD_ID|upper%\nlast";
+
+        let mut context = Context::new();
+        context.define("fabric");
+        context.put("MOD_ID", "my_mod");
+        let parts = read_template_with_partials(template, &partials).unwrap();
+        let unresolved = apply_template_with_mode(&context, parts, RenderMode::Strict, "build.gradle").unwrap_err();
+        assert_eq!(unresolved.to_string(), "Unresolved template placeholders:\n  %MISSING% in shadow.gradle (included in build.gradle):4");
+        let parts = read_template_with_partials(template, &partials).unwrap();
+        assert_eq!(apply_template(&context, parts), ["first", "developmentMY_MOD", "my_mod %MISSING%", "last"]);
+
+        let error = |template: &str| read_template_with_partials(template, &partials).err().unwrap();
+        assert_eq!(error("x\n//% include a"), "Include cycle a -> b -> a on line 1 in partial b on line 1 in partial a on line 2");
+        assert_eq!(error("//% include c"), "Unknown partial 'c' on line 1");
+        assert_eq!(error("//% include a platform"), "Invalid include argument 'platform', expected KEY=value on line 1");
+    }
+
+    /// Reads the non-Rust files in a directory of the bundled templates.
+    fn read_bundled_templates(dir: &std::path::Path, templates: &mut Vec<(std::path::PathBuf, String)>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                read_bundled_templates(&path, templates);
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                continue;
+            } else if let Ok(text) = std::fs::read_to_string(&path) {
+                templates.push((path, text));
+            }
+        }
+    }
+
+    #[test]
+    fn bundled_templates_are_valid() {
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src/templates");
+        let mut partial_files = Vec::new();
+        read_bundled_templates(&root.join("partials"), &mut partial_files);
+        let mut partials = Partials::new();
+        for (path, text) in partial_files {
+            partials.insert(path.file_name().unwrap().to_str().unwrap(), text);
+        }
+
+        let mut templates = Vec::new();
+        read_bundled_templates(&root, &mut templates);
+        for (path, text) in templates {
+            if let Err(err) = read_front_matter(&text).and(read_template_with_partials(&text, &partials)) {
+                panic!("{}: {}", path.display(), err);
+            }
+        }
+    }
 }
@@ -1,8 +1,9 @@
+//%! when not kotlin
 package %PACKAGE_NAME%.fabric.client;
 
 import net.fabricmc.api.ClientModInitializer;
 
-public final class ExampleModFabricClient implements ClientModInitializer {
+public final class %MAIN_CLASS_NAME%FabricClient implements ClientModInitializer {
     @Override
     public void onInitializeClient() {
         // This entrypoint is suitable for setting up client-specific logic, such as rendering.
@@ -0,0 +1,10 @@
+//%! when kotlin
+package %PACKAGE_NAME%.fabric.client
+
+import net.fabricmc.api.ClientModInitializer
+
+object %MAIN_CLASS_NAME%FabricClient : ClientModInitializer {
+    override fun onInitializeClient() {
+        // This entrypoint is suitable for setting up client-specific logic, such as rendering.
+    }
+}
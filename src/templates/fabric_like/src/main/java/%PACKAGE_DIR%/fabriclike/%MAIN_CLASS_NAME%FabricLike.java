@@ -0,0 +1,11 @@
+//%! when not kotlin
+package %PACKAGE_NAME%.fabriclike;
+
+import %PACKAGE_NAME%.%MAIN_CLASS_NAME%;
+
+public final class %MAIN_CLASS_NAME%FabricLike {
+    public static void init() {
+        // Run our common setup.
+        %MAIN_CLASS_NAME%.init();
+    }
+}
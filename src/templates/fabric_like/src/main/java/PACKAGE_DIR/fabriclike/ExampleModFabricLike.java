@@ -1,10 +0,0 @@
-package %PACKAGE_NAME%.fabriclike;
-
-import %PACKAGE_NAME%.ExampleMod;
-
-public final class ExampleModFabricLike {
-    public static void init() {
-        // Run our common setup.
-        ExampleMod.init();
-    }
-}
@@ -0,0 +1,11 @@
+//%! when kotlin
+package %PACKAGE_NAME%.fabriclike
+
+import %PACKAGE_NAME%.%MAIN_CLASS_NAME%
+
+object %MAIN_CLASS_NAME%FabricLike {
+    fun init() {
+        // Run our common setup.
+        %MAIN_CLASS_NAME%.init()
+    }
+}
@@ -0,0 +1,15 @@
+//%! when not kotlin
+package %PACKAGE_NAME%;
+
+import net.fabricmc.api.ModInitializer;
+
+public final class %MAIN_CLASS_NAME% implements ModInitializer {
+    public static final String MOD_ID = "%MOD_ID%";
+
+    @Override
+    public void onInitialize() {
+        // This code runs as soon as Minecraft is in a mod-load-ready state.
+        // However, some things (like resources) may still be uninitialized.
+        // Proceed with mild caution.
+    }
+}
@@ -0,0 +1,11 @@
+//%! when not kotlin
+package %PACKAGE_NAME%.client;
+
+import net.fabricmc.api.ClientModInitializer;
+
+public final class %MAIN_CLASS_NAME%Client implements ClientModInitializer {
+    @Override
+    public void onInitializeClient() {
+        // This entrypoint is suitable for setting up client-specific logic, such as rendering.
+    }
+}
@@ -0,0 +1,14 @@
+//%! when kotlin
+package %PACKAGE_NAME%
+
+import net.fabricmc.api.ModInitializer
+
+object %MAIN_CLASS_NAME% : ModInitializer {
+    const val MOD_ID = "%MOD_ID%"
+
+    override fun onInitialize() {
+        // This code runs as soon as Minecraft is in a mod-load-ready state.
+        // However, some things (like resources) may still be uninitialized.
+        // Proceed with mild caution.
+    }
+}
@@ -0,0 +1,33 @@
+//%! when kotlin
+package %PACKAGE_NAME%.forge
+
+//% if architectury_api
+import %ARCHITECTURY_PACKAGE%.platform.forge.EventBuses
+//% end
+import net.minecraftforge.fml.common.Mod
+//% if architectury_api
+import thedarkcolour.kotlinforforge.forge.MOD_BUS
+//% end
+
+//% if forge_like
+import %PACKAGE_NAME%.forgelike.%MAIN_CLASS_NAME%ForgeLike
+//% end
+import %PACKAGE_NAME%.%MAIN_CLASS_NAME%
+
+@Mod(%MAIN_CLASS_NAME%.MOD_ID)
+object %MAIN_CLASS_NAME%Forge {
+    init {
+//% if architectury_api
+        // Submit our event bus to let Architectury API register our content on the right time.
+        EventBuses.registerModEventBus(%MAIN_CLASS_NAME%.MOD_ID, MOD_BUS)
+
+//% end
+//% if forge_like
+        // Run the Forge-like setup.
+        %MAIN_CLASS_NAME%ForgeLike.init()
+//% else
+        // Run our common setup.
+        %MAIN_CLASS_NAME%.init()
+//% end
+    }
+}
@@ -0,0 +1,11 @@
+//%! when not kotlin
+package %PACKAGE_NAME%.forgelike;
+
+import %PACKAGE_NAME%.%MAIN_CLASS_NAME%;
+
+public final class %MAIN_CLASS_NAME%ForgeLike {
+    public static void init() {
+        // Run our common setup.
+        %MAIN_CLASS_NAME%.init();
+    }
+}
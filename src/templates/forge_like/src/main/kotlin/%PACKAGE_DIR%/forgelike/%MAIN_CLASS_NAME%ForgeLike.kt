@@ -0,0 +1,11 @@
+//%! when kotlin
+package %PACKAGE_NAME%.forgelike
+
+import %PACKAGE_NAME%.%MAIN_CLASS_NAME%
+
+object %MAIN_CLASS_NAME%ForgeLike {
+    fun init() {
+        // Run our common setup.
+        %MAIN_CLASS_NAME%.init()
+    }
+}
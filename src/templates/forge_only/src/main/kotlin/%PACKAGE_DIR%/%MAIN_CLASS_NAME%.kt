@@ -0,0 +1,15 @@
+//%! when kotlin
+package %PACKAGE_NAME%
+
+import net.minecraftforge.fml.common.Mod
+
+@Mod(%MAIN_CLASS_NAME%.MOD_ID)
+object %MAIN_CLASS_NAME% {
+    const val MOD_ID = "%MOD_ID%"
+
+    init {
+        // This code runs as soon as Minecraft is in a mod-load-ready state.
+        // However, some things (like registries and resources) may still be uninitialized.
+        // Proceed with mild caution.
+    }
+}
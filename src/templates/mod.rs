@@ -8,12 +8,17 @@ use crate::filer::FilePermissions;
 pub mod engine;
 pub mod fabric;
 pub mod fabric_like;
+pub mod fabric_only;
 pub mod forge;
+pub mod forge_like;
 pub mod forge_only;
 pub mod multiplatform;
 pub mod neoforge;
+pub mod neoforge_moddev;
 pub mod neoforge_only;
+pub mod partials;
 pub mod quilt;
+pub mod quilt_only;
 pub mod shared;
 
 pub struct FileData {
@@ -99,27 +104,35 @@ pub async fn download_relative_binary(
     Ok(response.bytes().await?)
 }
 
-macro_rules! file_data {
-    ($const_name:ident $fn_name:ident, $dir:expr, $include_dir_in_target:expr, $file_name:expr) => {
-        crate::templates::file_data!($const_name $fn_name, $dir, $include_dir_in_target, $file_name, None);
+/// Defines the file lists of a template directory, which are generated by the build script.
+/// `$dir` is the name of the directory in the generated project (and in the web build, with `_` instead of `-`),
+/// and `$include_dir_in_target` controls whether the files are placed in it.
+macro_rules! template_files {
+    ($module:literal, $dir:literal, $include_dir_in_target:literal) => {
+        const TEMPLATE_DIR: &str = $dir;
+        const INCLUDE_DIR_IN_TARGET: bool = $include_dir_in_target;
+
+        include!(concat!(env!("OUT_DIR"), "/templates/", $module, ".rs"));
     };
+}
 
-    ($const_name:ident $fn_name:ident, $dir:expr, $include_dir_in_target:expr, $file_name:expr, $permissions:ident) => {
-        crate::templates::file_data_raw!($const_name, $fn_name, $dir, $include_dir_in_target, $file_name, $permissions, str, include_str, Text, download_relative_text);
+macro_rules! file_data {
+    ($const_name:ident $fn_name:ident, $dir:expr, $include_dir_in_target:expr, $file_name:expr, $source_path:expr, $permissions:ident) => {
+        crate::templates::file_data_raw!($const_name, $fn_name, $dir, $include_dir_in_target, $file_name, $source_path, $permissions, str, include_str, Text, download_relative_text);
     };
 }
 
 macro_rules! binary_file_data {
-    ($const_name:ident $fn_name:ident, $dir:expr, $include_dir_in_target:expr, $file_name:expr) => {
-        crate::templates::file_data_raw!($const_name, $fn_name, $dir, $include_dir_in_target, $file_name, None, [u8], include_bytes, Binary, download_relative_binary);
+    ($const_name:ident $fn_name:ident, $dir:expr, $include_dir_in_target:expr, $file_name:expr, $source_path:expr, $permissions:ident) => {
+        crate::templates::file_data_raw!($const_name, $fn_name, $dir, $include_dir_in_target, $file_name, $source_path, $permissions, [u8], include_bytes, Binary, download_relative_binary);
     };
 }
 
 macro_rules! file_data_raw {
-    ($const_name:ident, $fn_name:ident, $dir:expr, $include_dir_in_target:expr, $file_name:expr, $permissions:ident,
+    ($const_name:ident, $fn_name:ident, $dir:expr, $include_dir_in_target:expr, $file_name:expr, $source_path:expr, $permissions:ident,
         $static_type:ty, $static_include:ident, $file_content_type:ident, $download_function:ident) => {
         #[cfg(not(target_family = "wasm"))]
-        const $const_name: &'static $static_type = $static_include!($file_name);
+        const $const_name: &'static $static_type = $static_include!($source_path);
 
         #[cfg(not(target_family = "wasm"))]
         async fn $fn_name(
@@ -140,7 +153,8 @@ macro_rules! file_data_raw {
         ) -> crate::Result<crate::templates::FileData> {
             let path =
                 crate::templates::compose_file_path($dir, $file_name, $include_dir_in_target);
-            let url = format!("templates/{}/{}", $dir.replace("-", "_"), $file_name);
+            // Escape the % of placeholders in file names so that they aren't decoded as URL escapes.
+            let url = format!("templates/{}/{}", $dir.replace("-", "_"), $file_name).replace('%', "%25");
             let bytes = crate::templates::$download_function(client, &url).await?;
             let content = crate::templates::FileContent::$file_content_type(bytes);
             let permissions = crate::filer::FilePermissions::$permissions;
@@ -152,10 +166,11 @@ macro_rules! file_data_raw {
 macro_rules! file_list {
     ($vis:vis $fn_name:ident, $($file_fn:ident)+) => {
         $vis async fn $fn_name(client: std::sync::Arc<reqwest::Client>) -> crate::Result<Vec<crate::templates::FileData>> {
-            let mut output: Vec<std::pin::Pin<Box<dyn std::future::Future<Output = crate::Result<crate::templates::FileData>>>>> = Vec::new();
-            $(
-            output.push(Box::pin($file_fn(client.clone())));
-            )+
+            let output: Vec<std::pin::Pin<Box<dyn std::future::Future<Output = crate::Result<crate::templates::FileData>>>>> = vec![
+                $(
+                Box::pin($file_fn(client.clone())),
+                )+
+            ];
             let results = futures::future::join_all(output).await;
             results.into_iter().collect()
         }
@@ -163,6 +178,7 @@ macro_rules! file_list {
 }
 
 pub(crate) use binary_file_data;
+pub(crate) use template_files;
 pub(crate) use file_data;
 pub(crate) use file_data_raw;
 pub(crate) use file_list;
@@ -1,6 +1,7 @@
+//%! when not kotlin
 package %PACKAGE_NAME%;
 
-public final class ExampleMod {
+public final class %MAIN_CLASS_NAME% {
     public static final String MOD_ID = "%MOD_ID%";
 
     public static void init() {
@@ -0,0 +1,10 @@
+//%! when kotlin
+package %PACKAGE_NAME%
+
+object %MAIN_CLASS_NAME% {
+    const val MOD_ID = "%MOD_ID%"
+
+    fun init() {
+        // Write common init code here.
+    }
+}
@@ -0,0 +1,22 @@
+//%! when kotlin
+package %PACKAGE_NAME%.neoforge
+
+import net.neoforged.fml.common.Mod
+
+//% if forge_like
+import %PACKAGE_NAME%.forgelike.%MAIN_CLASS_NAME%ForgeLike
+//% end
+import %PACKAGE_NAME%.%MAIN_CLASS_NAME%
+
+@Mod(%MAIN_CLASS_NAME%.MOD_ID)
+object %MAIN_CLASS_NAME%NeoForge {
+    init {
+//% if forge_like
+        // Run the Forge-like setup.
+        %MAIN_CLASS_NAME%ForgeLike.init()
+//% else
+        // Run our common setup.
+        %MAIN_CLASS_NAME%.init()
+//% end
+    }
+}
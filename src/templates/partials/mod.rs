@@ -0,0 +1,21 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use super::engine::Partials;
+use super::{FileContent, FileData};
+use crate::{err, Result};
+
+super::template_files!("partials", "partials", false);
+
+/// Loads all partials, keyed by their file names.
+pub async fn load_partials(client: std::sync::Arc<reqwest::Client>) -> Result<Partials> {
+    let mut partials = Partials::new();
+    for FileData { path, content, .. } in all_files(client).await? {
+        match content {
+            FileContent::Text(text) => partials.insert(path, text),
+            FileContent::Binary(_) => return Err(err!("Partial {} is not a text file", path)),
+        }
+    }
+    Ok(partials)
+}
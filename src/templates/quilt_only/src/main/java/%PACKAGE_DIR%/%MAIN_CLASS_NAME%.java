@@ -0,0 +1,16 @@
+//%! when not kotlin
+package %PACKAGE_NAME%;
+
+import org.quiltmc.loader.api.ModContainer;
+import org.quiltmc.qsl.base.api.entrypoint.ModInitializer;
+
+public final class %MAIN_CLASS_NAME% implements ModInitializer {
+    public static final String MOD_ID = "%MOD_ID%";
+
+    @Override
+    public void onInitialize(ModContainer mod) {
+        // This code runs as soon as Minecraft is in a mod-load-ready state.
+        // However, some things (like resources) may still be uninitialized.
+        // Proceed with mild caution.
+    }
+}
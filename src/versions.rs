@@ -3,11 +3,15 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 pub mod index;
+pub mod pins;
 mod version_metadata;
 pub use version_metadata::*;
 
-pub const LOOM_VERSION: &'static str = "1.11-SNAPSHOT";
-pub const PLUGIN_VERSION: &'static str = "3.4-SNAPSHOT";
+pub const LOOM_VERSION: &str = "1.11-SNAPSHOT";
+pub const PLUGIN_VERSION: &str = "3.4-SNAPSHOT";
+/// The Kotlin Gradle plugin version, which has to support the `compilerOptions` DSL and the Gradle version
+/// of the wrapper whatever Kotlin the language adapters bundle.
+pub const KOTLIN_PLUGIN_VERSION: &str = "2.2.0";
 
 #[cfg(target_family = "wasm")]
 pub async fn load_minecraft_version_list(client: std::sync::Arc<reqwest::Client>) -> crate::Result<String> {
@@ -3,12 +3,14 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
-use reqwest::Client;
+use futures::{try_join, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 
-use crate::Result;
-use crate::maven::{MavenLibrary, resolve_matching_version};
+use crate::{err, Result};
+use crate::maven::{MavenClient, MavenLibrary, MavenVersions, MissingMetadata};
+use super::pins::{VersionConstraint, VersionPins};
 use super::{MinecraftVersion, MinecraftVersionList};
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -17,127 +19,683 @@ pub struct VersionIndex {
     pub versions: HashMap<String, Versions>,
 }
 
+/// The maximum number of game versions that are resolved at the same time.
+const CONCURRENT_GAME_VERSIONS: usize = 16;
+
 impl VersionIndex {
-    pub async fn resolve(client: &reqwest::Client, version_list: &MinecraftVersionList) -> Result<Self> {
-        let mut versions: HashMap<String, Versions> = HashMap::new();
-
-        // TODO: Iterate in parallel?
-        for game_version in version_list.versions.iter() {
-            versions.insert(
-                game_version.version.to_owned(),
-                Versions::resolve(client, &game_version).await?,
-            );
-        }
+    pub async fn resolve(client: &MavenClient, version_list: &MinecraftVersionList) -> Result<Self> {
+        let metadata = MetadataStore::new(client);
+        let pins = VersionPins::default();
+        let versions: HashMap<String, Versions> = futures::stream::iter(&version_list.versions)
+            .map(|game_version| {
+                let metadata = &metadata;
+                let pins = &pins;
+                async move {
+                    let versions = Versions::resolve_for_index(metadata, game_version, pins).await?;
+                    Ok::<_, crate::result::Error>((game_version.version.clone(), versions))
+                }
+            })
+            .buffer_unordered(CONCURRENT_GAME_VERSIONS)
+            .try_collect()
+            .await?;
 
         Ok(Self { versions })
     }
 }
 
-#[derive(Clone, Serialize, Deserialize)]
+impl VersionIndex {
+    pub fn get(&self, game_version: &MinecraftVersion) -> Result<&Versions> {
+        self.versions
+            .get(&game_version.version)
+            .ok_or_else(|| {
+                err!(
+                    "Could not find version index for version {}",
+                    game_version.version
+                )
+            })
+    }
+}
+
+/// Where the generator gets dependency versions from.
+pub enum VersionSource {
+    /// Resolve versions from Maven.
+    /// On the web, the prebuilt version index is used for the versions included in it.
+    Online(MavenClient),
+    /// Only use versions from a local version index.
+    Offline(VersionIndex),
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct Versions {
     pub architectury_api: Option<String>,
     pub forge: Option<String>,
     pub neoforge: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub neoforge_yarn_patch: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fabric_loader: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fabric_api: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub yarn: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quilt_loader: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quilted_fabric_api: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parchment: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moddevgradle: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fabric_language_kotlin: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kotlin_for_forge: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kotlin_for_forge_neoforge: Option<String>,
 }
 
 impl Versions {
     pub async fn resolve(
-        client: &reqwest::Client,
+        client: &MavenClient,
         game_version: &MinecraftVersion,
+        pins: &VersionPins,
     ) -> Result<Self> {
-        let architectury_api = if let Some(arch_api) = &game_version.architectury.api_version {
-            Some(resolve_matching_version(
-                &client,
+        Self::resolve_from(&MetadataStore::new(client), game_version, pins).await
+    }
+
+    async fn resolve_from(
+        metadata: &MetadataStore<'_>,
+        game_version: &MinecraftVersion,
+        pins: &VersionPins,
+    ) -> Result<Self> {
+        let architectury_api = async {
+            if game_version.architectury.api_version.is_none() {
+                return Ok(None);
+            }
+            metadata.resolve_pinned_version(
                 MavenLibrary::architectury_api(game_version),
-                |version| version.starts_with(&format!("{}.", arch_api)),
+                pins.architectury_api.as_ref(),
+                |version| is_architectury_api_version_for(game_version, version),
             )
-            .await?)
-        } else {
-            None
+            .await
+            .map(Some)
         };
 
-        let forge = if let Some(forge) = &game_version.forge {
-            Some(resolve_matching_version(
-                &client,
+        let forge = async {
+            if game_version.forge.is_none() {
+                return Ok(None);
+            }
+            metadata.resolve_pinned_version(
                 MavenLibrary::forge(),
-                |version| {
-                    version.starts_with(&format!(
-                        "{}-{}.",
-                        game_version.version,
-                        forge.major_version
-                    ))
-                },
+                pins.forge.as_ref(),
+                |version| is_forge_version_for(game_version, version),
             )
-            .await?)
-        } else {
-            None
+            .await
+            .map(Some)
         };
 
-        let neoforge = if let Some(neoforge) = &game_version.neoforge {
-            Some(
-                resolve_matching_version(
-                    &client,
-                    MavenLibrary::neoforge(),
-                    |version| version.starts_with(&format!("{}.", neoforge.neoforge_major_version)),
-                )
-                .await?,
+        let neoforge = async {
+            if game_version.neoforge.is_none() {
+                return Ok(None);
+            }
+            metadata.resolve_pinned_version(
+                MavenLibrary::neoforge(),
+                pins.neoforge.as_ref(),
+                |version| is_neoforge_version_for(game_version, version),
             )
-        } else {
-            None
+            .await
+            .map(Some)
         };
 
-        let neoforge_yarn_patch = match &game_version.neoforge {
-            Some(neoforge) => match &neoforge.yarn_patch_version {
-                Some(prefix) => Some(
-                    resolve_matching_version(
-                        &client,
-                        MavenLibrary::neoforge_yarn_patch(),
-                        |version| version.starts_with(&format!("{}+", prefix)),
-                    )
-                    .await?,
-                ),
-                None => None,
-            },
-            None => None,
+        let neoforge_yarn_patch = async {
+            let Some(prefix) = game_version.neoforge.as_ref().and_then(|neoforge| neoforge.yarn_patch_version.as_ref()) else {
+                return Ok(None);
+            };
+            metadata.resolve_pinned_version(
+                MavenLibrary::neoforge_yarn_patch(),
+                None,
+                |version| version.starts_with(&format!("{}+", prefix)),
+            )
+            .await
+            .map(Some)
         };
 
+        let (architectury_api, forge, neoforge, neoforge_yarn_patch) =
+            try_join!(architectury_api, forge, neoforge, neoforge_yarn_patch)?;
+
         Ok(Self {
             architectury_api,
             forge,
             neoforge,
             neoforge_yarn_patch,
+            ..Default::default()
         })
     }
+
+    /// Resolves all versions of a game version for a version index.
+    async fn resolve_for_index(
+        metadata: &MetadataStore<'_>,
+        game_version: &MinecraftVersion,
+        pins: &VersionPins,
+    ) -> Result<Self> {
+        let mut versions = Self::resolve_from(metadata, game_version, pins).await?;
+        versions.resolve_index_only(metadata, game_version, pins).await?;
+        Ok(versions)
+    }
+
+    /// Resolves the libraries that generating a project only looks up when the project uses them,
+    /// so they're only added to version indexes.
+    async fn resolve_index_only(
+        &mut self,
+        metadata: &MetadataStore<'_>,
+        game_version: &MinecraftVersion,
+        pins: &VersionPins,
+    ) -> Result<()> {
+        let fabric_loader = async {
+            metadata.resolve_pinned_latest_version(MavenLibrary::fabric_loader(), pins.fabric_loader.as_ref())
+                .await
+                .map(Some)
+        };
+        let fabric_api_library = MavenLibrary::fabric_api();
+        let fabric_api = metadata.find_pinned_version(
+            &fabric_api_library,
+            pins.fabric_api.as_ref(),
+            |version| is_fabric_api_version_for(game_version, version),
+        );
+        let yarn_library = MavenLibrary::yarn();
+        let yarn = metadata.find_pinned_version(
+            &yarn_library,
+            pins.yarn.as_ref(),
+            |version| is_yarn_version_for(game_version, version),
+        );
+        let quilt_loader = async {
+            metadata.resolve_pinned_latest_version(MavenLibrary::quilt_loader(), pins.quilt_loader.as_ref())
+                .await
+                .map(Some)
+        };
+        let quilted_fabric_api_library = MavenLibrary::quilted_fabric_api();
+        let quilted_fabric_api = metadata.find_pinned_version(
+            &quilted_fabric_api_library,
+            pins.quilted_fabric_api.as_ref(),
+            |version| is_quilted_fabric_api_version_for(game_version, version),
+        );
+
+        // Parchment isn't released for every game version, so a missing artifact isn't an error.
+        let parchment = async {
+            let library = MavenLibrary::parchment(game_version);
+            let version = match metadata.find_pinned_latest_version(&library, pins.parchment.as_ref()).await {
+                Err(error) if error.downcast_ref::<MissingMetadata>().is_some() => None,
+                result => result?,
+            };
+            match (version, pins.parchment.as_ref()) {
+                (None, Some(pin)) => Err(err!("Could not find a version of {} matching {}", library, pin)),
+                (version, _) => Ok(version),
+            }
+        };
+
+        let moddevgradle = async {
+            if game_version.neoforge.is_none() {
+                return Ok(None);
+            }
+            metadata.resolve_pinned_latest_version(MavenLibrary::moddevgradle(), pins.moddevgradle.as_ref())
+                .await
+                .map(Some)
+        };
+
+        // Language adapters are only needed by Kotlin projects.
+        let fabric_language_kotlin = async {
+            metadata.resolve_pinned_latest_version(MavenLibrary::fabric_language_kotlin(), pins.fabric_language_kotlin.as_ref())
+                .await
+                .map(Some)
+        };
+        let kotlin_for_forge = async {
+            let Some(major) = game_version.forge.as_ref().and_then(|forge| forge.kotlin_for_forge_version.as_ref()) else {
+                return Ok(None);
+            };
+            metadata.resolve_pinned_version(
+                MavenLibrary::kotlin_for_forge(),
+                pins.kotlin_for_forge.as_ref(),
+                |version| is_kotlin_for_forge_version_for(major, version),
+            )
+            .await
+            .map(Some)
+        };
+        let kotlin_for_forge_neoforge = async {
+            let Some(major) = game_version.neoforge.as_ref().and_then(|neoforge| neoforge.kotlin_for_forge_version.as_ref()) else {
+                return Ok(None);
+            };
+            metadata.resolve_pinned_version(
+                MavenLibrary::kotlin_for_forge_neoforge(),
+                pins.kotlin_for_forge_neoforge.as_ref(),
+                |version| is_kotlin_for_forge_version_for(major, version),
+            )
+            .await
+            .map(Some)
+        };
+
+        (
+            self.fabric_loader,
+            self.fabric_api,
+            self.yarn,
+            self.quilt_loader,
+            self.quilted_fabric_api,
+            self.parchment,
+            self.moddevgradle,
+            self.fabric_language_kotlin,
+            self.kotlin_for_forge,
+            self.kotlin_for_forge_neoforge,
+        ) = try_join!(
+            fabric_loader,
+            fabric_api,
+            yarn,
+            quilt_loader,
+            quilted_fabric_api,
+            parchment,
+            moddevgradle,
+            fabric_language_kotlin,
+            kotlin_for_forge,
+            kotlin_for_forge_neoforge,
+        )?;
+        Ok(())
+    }
+
+    /// Replaces versions that have an exact pin, and checks that the other
+    /// versions satisfy their constraints. Used for versions that come from a version index.
+    pub fn apply_pins(&mut self, pins: &VersionPins) -> Result<()> {
+        let versions = [
+            ("fabric_loader", &mut self.fabric_loader),
+            ("fabric_api", &mut self.fabric_api),
+            ("forge", &mut self.forge),
+            ("neoforge", &mut self.neoforge),
+            ("architectury_api", &mut self.architectury_api),
+            ("yarn", &mut self.yarn),
+            ("quilt_loader", &mut self.quilt_loader),
+            ("quilted_fabric_api", &mut self.quilted_fabric_api),
+            ("parchment", &mut self.parchment),
+            ("moddevgradle", &mut self.moddevgradle),
+            ("fabric_language_kotlin", &mut self.fabric_language_kotlin),
+            ("kotlin_for_forge", &mut self.kotlin_for_forge),
+            ("kotlin_for_forge_neoforge", &mut self.kotlin_for_forge_neoforge),
+        ];
+
+        for (key, version) in versions {
+            let Some(pin) = pins.get(key) else {
+                continue;
+            };
+
+            if let Some(exact) = pin.exact() {
+                *version = Some(exact.to_owned());
+            } else if let Some(version) = version {
+                if !pin.matches(version) {
+                    return Err(err!("The indexed {} version {} does not match the pin {}", key, version, pin));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+type MetadataEntry = Arc<futures::lock::Mutex<Option<Arc<MavenVersions>>>>;
+
+/// Maven metadata that is shared between version lookups,
+/// so that the metadata of each library is only downloaded once.
+pub struct MetadataStore<'a> {
+    client: &'a MavenClient,
+    entries: Mutex<HashMap<String, MetadataEntry>>,
+}
+
+impl<'a> MetadataStore<'a> {
+    pub fn new(client: &'a MavenClient) -> Self {
+        Self {
+            client,
+            entries: Default::default(),
+        }
+    }
+
+    /// Returns the versions of a library, downloading them if no other lookup has done so yet.
+    pub async fn get(&self, library: &MavenLibrary) -> Result<Arc<MavenVersions>> {
+        let key = format!("{}:{}:{}", library.repository().key(), library.group(), library.name());
+        let entry = self.entries.lock().unwrap().entry(key).or_default().clone();
+        // Concurrent lookups of the same library wait here until the first one has downloaded it.
+        let mut entry = entry.lock().await;
+        if let Some(versions) = entry.as_ref() {
+            return Ok(versions.clone());
+        }
+
+        let versions = Arc::new(MavenVersions::download(self.client, library).await?);
+        *entry = Some(versions.clone());
+        Ok(versions)
+    }
+
+    /// Finds the latest version of a library that matches both the filter and the pin.
+    /// Exact pins are returned as is without downloading the Maven metadata.
+    pub async fn find_pinned_version<F>(
+        &self,
+        library: &MavenLibrary,
+        pin: Option<&VersionConstraint>,
+        filter: F,
+    ) -> Result<Option<String>>
+    where
+        F: Fn(&str) -> bool,
+    {
+        if let Some(VersionConstraint::Exact(version)) = pin {
+            return Ok(Some(version.clone()));
+        }
+
+        let versions = self.get(library).await?;
+        let version = match pin {
+            Some(pin) => versions.find_matching(|version| filter(version) && pin.matches(version)),
+            None => versions.find_matching(filter),
+        };
+        Ok(version.map(str::to_owned))
+    }
+
+    pub async fn resolve_pinned_version<F>(
+        &self,
+        library: MavenLibrary,
+        pin: Option<&VersionConstraint>,
+        filter: F,
+    ) -> Result<String>
+    where
+        F: Fn(&str) -> bool,
+    {
+        self.find_pinned_version(&library, pin, filter)
+            .await?
+            .ok_or_else(|| match pin {
+                Some(pin) => err!("Could not find a version of {} matching {}", library, pin),
+                None => err!("Could not find latest version for {}", library),
+            })
+    }
+
+    /// Finds the latest version of a library, or the latest version matching the pin.
+    pub async fn find_pinned_latest_version(
+        &self,
+        library: &MavenLibrary,
+        pin: Option<&VersionConstraint>,
+    ) -> Result<Option<String>> {
+        if pin.is_some() {
+            return self.find_pinned_version(library, pin, |_| true).await;
+        }
+
+        Ok(self.get(library).await?.latest().map(str::to_owned))
+    }
+
+    /// Resolves the latest version of a library, or the latest version matching the pin.
+    pub async fn resolve_pinned_latest_version(
+        &self,
+        library: MavenLibrary,
+        pin: Option<&VersionConstraint>,
+    ) -> Result<String> {
+        if pin.is_some() {
+            return self.resolve_pinned_version(library, pin, |_| true).await;
+        }
+
+        self.find_pinned_latest_version(&library, pin)
+            .await?
+            .ok_or_else(|| err!("Could not find latest version for {}", library))
+    }
+}
+
+pub async fn resolve_pinned_version<F>(
+    client: &MavenClient,
+    library: MavenLibrary,
+    pin: Option<&VersionConstraint>,
+    filter: F,
+) -> Result<String>
+where
+    F: Fn(&str) -> bool,
+{
+    MetadataStore::new(client).resolve_pinned_version(library, pin, filter).await
+}
+
+pub async fn resolve_pinned_latest_version(
+    client: &MavenClient,
+    library: MavenLibrary,
+    pin: Option<&VersionConstraint>,
+) -> Result<String> {
+    MetadataStore::new(client).resolve_pinned_latest_version(library, pin).await
+}
+
+pub fn is_architectury_api_version_for(game_version: &MinecraftVersion, version: &str) -> bool {
+    game_version.architectury.api_version.as_ref()
+        .is_some_and(|api_version| version.starts_with(&format!("{}.", api_version)))
+}
+
+/// Checks whether a Kotlin for Forge version has the major version of a game version, e.g. `4` for 1.20.1.
+pub fn is_kotlin_for_forge_version_for(major_version: &str, version: &str) -> bool {
+    version.starts_with(&format!("{}.", major_version))
+}
+
+pub fn is_forge_version_for(game_version: &MinecraftVersion, version: &str) -> bool {
+    game_version.forge.as_ref().is_some_and(|forge| {
+        version.starts_with(&format!("{}-{}.", game_version.version, forge.major_version))
+    })
+}
+
+pub fn is_neoforge_version_for(game_version: &MinecraftVersion, version: &str) -> bool {
+    game_version.neoforge.as_ref()
+        .is_some_and(|neoforge| version.starts_with(&format!("{}.", neoforge.neoforge_major_version)))
+}
+
+pub fn is_yarn_version_for(game_version: &MinecraftVersion, version: &str) -> bool {
+    version.starts_with(&format!("{}+", game_version.version))
+}
+
+pub fn is_fabric_api_version_for(game_version: &MinecraftVersion, version: &str) -> bool {
+    let branch = game_version.fabric.fabric_api_branch.as_ref().unwrap_or(&game_version.version);
+    version.ends_with(&format!("+{}", branch))
+}
+
+pub fn is_quilted_fabric_api_version_for(game_version: &MinecraftVersion, version: &str) -> bool {
+    version.ends_with(&format!("-{}", game_version.version))
 }
 
+/// Whether [`get_version_index`] returns every version of the version index,
+/// and not only the versions that [`Versions::resolve`] resolves online.
+pub const VERSION_INDEX_IS_COMPLETE: bool = cfg!(target_family = "wasm");
+
 #[cfg(target_family = "wasm")]
 pub async fn get_version_index(
-    client: std::sync::Arc<Client>,
+    client: &MavenClient,
     game_version: &MinecraftVersion,
+    pins: &VersionPins,
 ) -> Result<Versions> {
-    use crate::err;
-    use crate::versions::index::VersionIndex;
-
+    let client = std::sync::Arc::new(client.http().clone());
     let json = crate::templates::download_relative_text(client, "version_index.json").await?;
     let index: VersionIndex = serde_json::from_str(&json)?;
-    index
-        .versions
-        .get(&game_version.version)
-        .ok_or_else(|| {
-            err!(
-                "Could not find version index for version {}",
-                game_version.version
-            )
-        })
-        .cloned()
+    let mut versions = index.get(game_version)?.clone();
+    versions.apply_pins(pins)?;
+    Ok(versions)
 }
 
 #[cfg(not(target_family = "wasm"))]
 pub async fn get_version_index(
-    client: std::sync::Arc<Client>,
+    client: &MavenClient,
     game_version: &MinecraftVersion,
+    pins: &VersionPins,
 ) -> Result<Versions> {
-    Versions::resolve(&client, game_version).await
+    Versions::resolve(client, game_version, pins).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maven::MavenRepositoryUrls;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    type RequestCounts = Arc<Mutex<HashMap<String, usize>>>;
+
+    /// Serves the `maven-metadata.xml` files of the given libraries over HTTP on a local port,
+    /// and returns a client using it as every repository together with the number of requests per path.
+    /// Libraries without versions respond with a server error.
+    async fn stub_client(libraries: &[(&str, &str, &[&str])]) -> (MavenClient, RequestCounts) {
+        let files: HashMap<String, Option<String>> = libraries
+            .iter()
+            .map(|(group, artifact, versions)| {
+                let version_list: String = versions.iter().map(|v| format!("<version>{}</version>", v)).collect();
+                let metadata = versions.last().map(|latest| format!(
+                    "<metadata><versioning><latest>{}</latest><versions>{}</versions></versioning></metadata>",
+                    latest,
+                    version_list,
+                ));
+                (format!("/{}/{}/maven-metadata.xml", group.replace('.', "/"), artifact), metadata)
+            })
+            .collect();
+        let files = Arc::new(files);
+        let requests = RequestCounts::default();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let counts = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let files = files.clone();
+                let counts = counts.clone();
+                tokio::spawn(async move {
+                    let mut request = Vec::new();
+                    let mut buffer = [0; 1024];
+                    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                        match stream.read(&mut buffer).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(read) => request.extend_from_slice(&buffer[..read]),
+                        }
+                    }
+                    let request = String::from_utf8_lossy(&request);
+                    let path = request.split_whitespace().nth(1).unwrap_or_default().to_owned();
+                    *counts.lock().unwrap().entry(path.clone()).or_default() += 1;
+                    let response = match files.get(&path) {
+                        Some(Some(body)) => format!(
+                            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                            body.len(),
+                            body,
+                        ),
+                        Some(None) => "HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_owned(),
+                        None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_owned(),
+                    };
+                    let _ = stream.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+
+        let urls = vec![url];
+        let client = MavenClient::new(reqwest::Client::builder().no_proxy().build().unwrap())
+            .with_repositories(MavenRepositoryUrls {
+                fabric: urls.clone(),
+                architectury: urls.clone(),
+                forge: urls.clone(),
+                neoforge: urls.clone(),
+                quilt: urls.clone(),
+                parchment: urls.clone(),
+                kotlin_for_forge: urls,
+            });
+        (client, requests)
+    }
+
+    const LIBRARIES: &[(&str, &str, &[&str])] = &[
+        ("net.fabricmc", "fabric-loader", &["0.16.9", "0.16.10"]),
+        ("net.fabricmc.fabric-api", "fabric-api", &["0.92.2+1.20.1", "0.110.0+1.21.1"]),
+        ("net.fabricmc", "yarn", &["1.20.1+build.10", "1.21.1+build.3"]),
+        ("org.quiltmc", "quilt-loader", &["0.27.1"]),
+        ("org.quiltmc.quilted-fabric-api", "quilted-fabric-api", &["7.6.0+0.92.2-1.20.1"]),
+        ("dev.architectury", "architectury", &["9.2.14", "13.0.8"]),
+        ("dev.architectury", "yarn-mappings-patch-neoforge", &["1.21+build.4"]),
+        ("net.minecraftforge", "forge", &["1.20.1-47.3.0", "1.20.1-47.3.12"]),
+        ("net.neoforged", "neoforge", &["21.1.90", "21.4.1"]),
+        ("net.neoforged", "moddev-gradle", &["2.0.80", "2.0.107"]),
+        ("net.fabricmc", "fabric-language-kotlin", &["1.13.4+kotlin.2.1.21"]),
+        ("thedarkcolour", "kotlinforforge", &["4.11.0", "5.9.0"]),
+        ("thedarkcolour", "kotlinforforge-neoforge", &["4.11.0", "5.9.0"]),
+        ("org.parchmentmc.data", "parchment-1.21.1", &["2024.07.28", "2024.11.17"]),
+    ];
+
+    /// The version list filtered to 1.20.1 and 1.21.1.
+    fn test_version_list() -> MinecraftVersionList {
+        let version_list: MinecraftVersionList = serde_json::from_str(include_str!("../minecraft_versions.json")).unwrap();
+        MinecraftVersionList {
+            versions: version_list.versions.into_iter()
+                .filter(|v| v.version == "1.20.1" || v.version == "1.21.1")
+                .collect(),
+            ..version_list
+        }
+    }
+
+    #[tokio::test]
+    async fn resolve_index_from_shared_metadata() {
+        let (client, requests) = stub_client(LIBRARIES).await;
+        let version_list = test_version_list();
+
+        let index = VersionIndex::resolve(&client, &version_list).await.unwrap();
+        let old = &index.versions["1.20.1"];
+        assert_eq!(old.forge.as_deref(), Some("1.20.1-47.3.12"));
+        assert_eq!(old.architectury_api.as_deref(), Some("9.2.14"));
+        assert_eq!(old.quilted_fabric_api.as_deref(), Some("7.6.0+0.92.2-1.20.1"));
+        let new = &index.versions["1.21.1"];
+        assert_eq!(new.neoforge.as_deref(), Some("21.1.90"));
+        assert_eq!(new.moddevgradle.as_deref(), Some("2.0.107"));
+        assert_eq!(old.moddevgradle, None);
+        assert_eq!(old.kotlin_for_forge.as_deref(), Some("4.11.0"));
+        assert_eq!(new.kotlin_for_forge_neoforge.as_deref(), Some("5.9.0"));
+        assert_eq!(new.fabric_api.as_deref(), Some("0.110.0+1.21.1"));
+        assert_eq!(new.quilted_fabric_api, None);
+        assert_eq!(new.fabric_loader, old.fabric_loader);
+        assert_eq!(new.parchment.as_deref(), Some("2024.11.17"));
+        // There's no Parchment for 1.20.1 on the stub Maven.
+        assert_eq!(old.parchment, None);
+
+        // Libraries shared by both game versions, like Fabric Loader and Fabric API,
+        // and by several dependencies, like Architectury's Maven group, are only downloaded once.
+        let requests = requests.lock().unwrap();
+        assert!(requests.contains_key("/net/fabricmc/fabric-loader/maven-metadata.xml"));
+        assert!(requests.contains_key("/net/fabricmc/fabric-api/fabric-api/maven-metadata.xml"));
+        for (path, count) in requests.iter() {
+            assert_eq!(*count, 1, "{} was requested {} times", path, count);
+        }
+    }
+
+    #[tokio::test]
+    async fn resolve_versions_for_generation() {
+        let version_list = test_version_list();
+        let game_version = version_list.versions.iter().find(|v| v.version == "1.20.1").unwrap();
+        let (client, requests) = stub_client(LIBRARIES).await;
+
+        // Pins of libraries that are only looked up when a project uses them aren't checked here.
+        let pins = VersionPins {
+            fabric_loader: Some(VersionConstraint::parse(">=99").unwrap()),
+            ..VersionPins::default()
+        };
+        let versions = Versions::resolve(&client, game_version, &pins).await.unwrap();
+        assert_eq!(versions.forge.as_deref(), Some("1.20.1-47.3.12"));
+        assert_eq!(versions.architectury_api.as_deref(), Some("9.2.14"));
+        assert_eq!(versions.fabric_loader, None);
+        assert_eq!(versions.fabric_language_kotlin, None);
+        let requests = requests.lock().unwrap();
+        let mut paths: Vec<_> = requests.keys().map(String::as_str).collect();
+        paths.sort();
+        assert_eq!(paths, [
+            "/dev/architectury/architectury/maven-metadata.xml",
+            "/net/minecraftforge/forge/maven-metadata.xml",
+        ]);
+    }
+
+    #[tokio::test]
+    async fn parchment_errors_are_not_ignored() {
+        let version_list = test_version_list();
+        let game_version = version_list.versions.iter().find(|v| v.version == "1.21.1").unwrap();
+
+        let pins = VersionPins {
+            parchment: Some(VersionConstraint::parse("2025.*").unwrap()),
+            ..VersionPins::default()
+        };
+        let (client, _) = stub_client(LIBRARIES).await;
+        let result = Versions::resolve_for_index(&MetadataStore::new(&client), game_version, &pins).await;
+        assert!(result.is_err_and(|error| error.to_string().contains("matching 2025.*")));
+
+        let libraries = [LIBRARIES, &[("org.parchmentmc.data", "parchment-1.20.1", &[])]].concat();
+        let (client, _) = stub_client(&libraries).await;
+        let game_version = version_list.versions.iter().find(|v| v.version == "1.20.1").unwrap();
+        let result = Versions::resolve_for_index(&MetadataStore::new(&client), game_version, &VersionPins::default()).await;
+        assert!(result.is_err_and(|error| error.to_string().contains("500")));
+    }
 }
@@ -0,0 +1,285 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt::Display;
+
+use crate::{err, Result};
+
+/// A user-specified constraint for a dependency version.
+///
+/// The syntax is one of:
+/// - an exact version, e.g. `0.16.9` or `=0.16.9`
+/// - a prefix ending in `*`, e.g. `0.16.*`
+/// - comma-separated comparisons, e.g. `>=0.16, <0.17`
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum VersionConstraint {
+    Exact(String),
+    Prefix(String),
+    Range(Vec<(Comparison, String)>),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Comparison {
+    fn symbol(&self) -> &'static str {
+        match self {
+            Self::Less => "<",
+            Self::LessOrEqual => "<=",
+            Self::Greater => ">",
+            Self::GreaterOrEqual => ">=",
+        }
+    }
+
+    fn accepts(&self, ordering: Ordering) -> bool {
+        match self {
+            Self::Less => ordering == Ordering::Less,
+            Self::LessOrEqual => ordering != Ordering::Greater,
+            Self::Greater => ordering == Ordering::Greater,
+            Self::GreaterOrEqual => ordering != Ordering::Less,
+        }
+    }
+}
+
+impl VersionConstraint {
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(err!("Version constraint is empty"));
+        }
+
+        if let Some(prefix) = input.strip_suffix('*') {
+            return Ok(Self::Prefix(prefix.to_owned()));
+        }
+
+        if !input.starts_with(['<', '>']) {
+            let version = input.strip_prefix('=').unwrap_or(input).trim();
+            return Ok(Self::Exact(version.to_owned()));
+        }
+
+        let mut comparisons = Vec::new();
+        for part in input.split(',') {
+            let part = part.trim();
+            let (comparison, version) = if let Some(version) = part.strip_prefix(">=") {
+                (Comparison::GreaterOrEqual, version)
+            } else if let Some(version) = part.strip_prefix("<=") {
+                (Comparison::LessOrEqual, version)
+            } else if let Some(version) = part.strip_prefix('>') {
+                (Comparison::Greater, version)
+            } else if let Some(version) = part.strip_prefix('<') {
+                (Comparison::Less, version)
+            } else {
+                return Err(err!("Invalid version comparison '{}' in '{}'", part, input));
+            };
+
+            let version = version.trim();
+            if version.is_empty() {
+                return Err(err!("Missing version in comparison '{}'", part));
+            }
+            comparisons.push((comparison, version.to_owned()));
+        }
+
+        Ok(Self::Range(comparisons))
+    }
+
+    /// Returns the pinned version if this constraint only allows a single version.
+    pub fn exact(&self) -> Option<&str> {
+        match self {
+            Self::Exact(version) => Some(version),
+            _ => None,
+        }
+    }
+
+    pub fn matches(&self, version: &str) -> bool {
+        match self {
+            Self::Exact(exact) => version == exact,
+            Self::Prefix(prefix) => version.starts_with(prefix.as_str()),
+            Self::Range(comparisons) => comparisons.iter().all(|(comparison, bound)| {
+                comparison.accepts(flexver_rs::compare(version, bound))
+            }),
+        }
+    }
+}
+
+impl Display for VersionConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exact(version) => write!(f, "{}", version),
+            Self::Prefix(prefix) => write!(f, "{}*", prefix),
+            Self::Range(comparisons) => {
+                let parts: Vec<String> = comparisons
+                    .iter()
+                    .map(|(comparison, version)| format!("{}{}", comparison.symbol(), version))
+                    .collect();
+                write!(f, "{}", parts.join(", "))
+            }
+        }
+    }
+}
+
+impl TryFrom<String> for VersionConstraint {
+    type Error = crate::result::Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        Self::parse(&value)
+    }
+}
+
+impl From<VersionConstraint> for String {
+    fn from(value: VersionConstraint) -> Self {
+        value.to_string()
+    }
+}
+
+/// Version constraints for the dependencies of a generated project.
+/// Dependencies without a constraint use the latest compatible version.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VersionPins {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fabric_loader: Option<VersionConstraint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fabric_api: Option<VersionConstraint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forge: Option<VersionConstraint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub neoforge: Option<VersionConstraint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub architectury_api: Option<VersionConstraint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub yarn: Option<VersionConstraint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quilt_loader: Option<VersionConstraint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quilted_fabric_api: Option<VersionConstraint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parchment: Option<VersionConstraint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub moddevgradle: Option<VersionConstraint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fabric_language_kotlin: Option<VersionConstraint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kotlin_for_forge: Option<VersionConstraint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kotlin_for_forge_neoforge: Option<VersionConstraint>,
+}
+
+impl VersionPins {
+    pub const KEYS: [&'static str; 13] = [
+        "fabric_loader",
+        "fabric_api",
+        "forge",
+        "neoforge",
+        "architectury_api",
+        "yarn",
+        "quilt_loader",
+        "quilted_fabric_api",
+        "parchment",
+        "moddevgradle",
+        "fabric_language_kotlin",
+        "kotlin_for_forge",
+        "kotlin_for_forge_neoforge",
+    ];
+
+    pub fn is_empty(&self) -> bool {
+        Self::KEYS.iter().all(|key| self.get(key).is_none())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&VersionConstraint> {
+        self.slot(key).and_then(|slot| slot.as_ref())
+    }
+
+    /// Pins a dependency by its key. Dashes in the key are treated as underscores.
+    pub fn set(&mut self, key: &str, constraint: VersionConstraint) -> Result<()> {
+        let key = key.replace('-', "_");
+        let slot = self.slot_mut(&key).ok_or_else(|| {
+            err!("Unknown dependency {}; expected one of {}", key, Self::KEYS.join(", "))
+        })?;
+        *slot = Some(constraint);
+        Ok(())
+    }
+
+    /// Copies every constraint in `other` into this set of pins.
+    pub fn merge(&mut self, other: VersionPins) {
+        for key in Self::KEYS {
+            if let Some(constraint) = other.get(key) {
+                *self.slot_mut(key).unwrap() = Some(constraint.clone());
+            }
+        }
+    }
+
+    fn slot(&self, key: &str) -> Option<&Option<VersionConstraint>> {
+        Some(match key {
+            "fabric_loader" => &self.fabric_loader,
+            "fabric_api" => &self.fabric_api,
+            "forge" => &self.forge,
+            "neoforge" => &self.neoforge,
+            "architectury_api" => &self.architectury_api,
+            "yarn" => &self.yarn,
+            "quilt_loader" => &self.quilt_loader,
+            "quilted_fabric_api" => &self.quilted_fabric_api,
+            "parchment" => &self.parchment,
+            "moddevgradle" => &self.moddevgradle,
+            "fabric_language_kotlin" => &self.fabric_language_kotlin,
+            "kotlin_for_forge" => &self.kotlin_for_forge,
+            "kotlin_for_forge_neoforge" => &self.kotlin_for_forge_neoforge,
+            _ => return None,
+        })
+    }
+
+    fn slot_mut(&mut self, key: &str) -> Option<&mut Option<VersionConstraint>> {
+        Some(match key {
+            "fabric_loader" => &mut self.fabric_loader,
+            "fabric_api" => &mut self.fabric_api,
+            "forge" => &mut self.forge,
+            "neoforge" => &mut self.neoforge,
+            "architectury_api" => &mut self.architectury_api,
+            "yarn" => &mut self.yarn,
+            "quilt_loader" => &mut self.quilt_loader,
+            "quilted_fabric_api" => &mut self.quilted_fabric_api,
+            "parchment" => &mut self.parchment,
+            "moddevgradle" => &mut self.moddevgradle,
+            "fabric_language_kotlin" => &mut self.fabric_language_kotlin,
+            "kotlin_for_forge" => &mut self.kotlin_for_forge,
+            "kotlin_for_forge_neoforge" => &mut self.kotlin_for_forge_neoforge,
+            _ => return None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_constraints() {
+        assert_eq!(VersionConstraint::parse("0.16.9").unwrap(), VersionConstraint::Exact("0.16.9".to_owned()));
+        assert_eq!(VersionConstraint::parse("=0.16.9").unwrap(), VersionConstraint::Exact("0.16.9".to_owned()));
+        assert_eq!(VersionConstraint::parse("0.16.*").unwrap(), VersionConstraint::Prefix("0.16.".to_owned()));
+        assert_eq!(
+            VersionConstraint::parse(">=0.16, <0.17").unwrap(),
+            VersionConstraint::Range(vec![
+                (Comparison::GreaterOrEqual, "0.16".to_owned()),
+                (Comparison::Less, "0.17".to_owned()),
+            ])
+        );
+        assert!(VersionConstraint::parse(">=0.16, 0.17").is_err());
+    }
+
+    #[test]
+    fn range_matching() {
+        let constraint = VersionConstraint::parse(">=0.16, <0.16.10").unwrap();
+        assert!(constraint.matches("0.16.9"));
+        assert!(!constraint.matches("0.16.10"));
+        assert!(!constraint.matches("0.15.11"));
+    }
+}
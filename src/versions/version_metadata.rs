@@ -16,7 +16,7 @@ impl<'a> MinecraftVersionMap<'a> {
         let mut versions_by_index: HashMap<&'a str, &'a MinecraftVersion> = HashMap::new();
 
         for version in &version_list.versions {
-            versions_by_index.insert(&version.version, &version);
+            versions_by_index.insert(&version.version, version);
         }
 
         let latest_version = versions_by_index[&version_list.latest_version.as_ref()];
@@ -91,6 +91,9 @@ pub struct ForgeMetadata {
     pub major_version: u32,
     pub pack_version: u32,
     pub server_pack_version: Option<(String, String)>,
+    /// The major version of Kotlin for Forge for this Forge version.
+    #[serde(default)]
+    pub kotlin_for_forge_version: Option<String>,
 }
 
 #[derive(Clone, Deserialize, Eq, PartialEq, Serialize)]
@@ -98,6 +101,9 @@ pub struct NeoForgeMetadata {
     pub loader_major_version: String,
     pub neoforge_major_version: String,
     pub yarn_patch_version: Option<String>,
+    /// The major version of Kotlin for Forge for this NeoForge version.
+    #[serde(default)]
+    pub kotlin_for_forge_version: Option<String>,
 }
 
 #[derive(Eq, PartialEq, Clone, Copy)]
@@ -7,6 +7,10 @@ use wasm_bindgen::prelude::*;
 
 use crate::Result;
 use crate::filer;
+use crate::maven::MavenClient;
+use crate::templates::engine::RenderMode;
+use crate::versions::index::{get_version_index, VersionSource};
+use crate::versions::pins::VersionPins;
 use crate::versions::{MinecraftVersion, MinecraftVersionList};
 
 fn ok_or_display_error<T, E>(result: Result<T, E>) -> Option<T>
@@ -50,7 +54,26 @@ pub fn is_valid_mod_id(mod_id: &str) -> bool {
 
 #[wasm_bindgen]
 pub fn validate_mod_id(mod_id: &str) -> Array {
-    let result = crate::mod_ids::validate_mod_id(mod_id);
+    validation_to_array(crate::mod_ids::validate_mod_id(mod_id))
+}
+
+#[wasm_bindgen]
+pub fn to_class_name(mod_name: &str) -> String {
+    crate::class_names::to_class_name(mod_name)
+}
+
+#[wasm_bindgen]
+pub fn is_valid_class_name(class_name: &str) -> bool {
+    crate::class_names::is_valid_class_name(class_name)
+}
+
+#[wasm_bindgen]
+pub fn validate_class_name(class_name: &str) -> Array {
+    validation_to_array(crate::class_names::validate_class_name(class_name))
+}
+
+/// Converts a validation result to `[true]` or `[false, error message]`.
+fn validation_to_array(result: Result<()>) -> Array {
     let array = Array::new();
     match result {
         Ok(_) => {
@@ -73,15 +96,22 @@ pub async fn generate(state: JsValue, version_list: JsValue) {
 async fn generate_inner(state: JsValue, version_list: JsValue) -> Result<(), JsValue> {
     let app: crate::app::GeneratorApp = serde_wasm_bindgen::from_value(state)?;
     let version_list: MinecraftVersionList = serde_wasm_bindgen::from_value(version_list)?;
-    crate::app::generator::generate(&app, &version_list, &filer::ZipFilerProvider(filer::web::ZipSaveDialog))
+    let client = reqwest::ClientBuilder::new().build().map_err(|err| JsValue::from(format!("{}", err)))?;
+    let version_source = VersionSource::Online(MavenClient::new(client));
+    crate::app::generator::generate(&app, &version_list, &version_source, RenderMode::Lenient, &filer::ZipFilerProvider(filer::web::ZipSaveDialog))
         .await
         .map_err(|err| JsValue::from(format!("{}", err)))
 }
 
+/// Checks whether the version index has a Parchment version for the game version.
 #[wasm_bindgen]
-pub fn supports_arch_api(game_version: JsValue) -> Result<bool, JsValue> {
+pub async fn supports_parchment(game_version: JsValue) -> Result<bool, JsValue> {
     let game_version: MinecraftVersion = serde_wasm_bindgen::from_value(game_version)?;
-    Ok(game_version.architectury.api_version.is_some())
+    let client = reqwest::ClientBuilder::new().build().map_err(|err| JsValue::from(format!("{}", err)))?;
+    let versions = get_version_index(&MavenClient::new(client), &game_version, &VersionPins::default())
+        .await
+        .map_err(|err| JsValue::from(format!("{}", err)))?;
+    Ok(versions.parchment.is_some())
 }
 
 #[wasm_bindgen]
@@ -97,11 +127,8 @@ pub fn supports_forge(game_version: JsValue) -> Result<bool, JsValue> {
 }
 
 #[wasm_bindgen]
-pub fn arch_api_supports_forge(game_version: JsValue) -> Result<bool, JsValue> {
+pub fn supports_arch_api(game_version: JsValue, subprojects: JsValue) -> Result<bool, JsValue> {
     let game_version: MinecraftVersion = serde_wasm_bindgen::from_value(game_version)?;
-    if let Some(forge) = game_version.forge {
-        Ok(forge.major_version < 50)
-    } else {
-        Ok(false)
-    }
+    let subprojects: crate::Subprojects = serde_wasm_bindgen::from_value(subprojects)?;
+    Ok(subprojects.supports_architectury_api(&game_version))
 }
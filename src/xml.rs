@@ -46,7 +46,7 @@ mod native {
             } else {
                 self.0.get_elements_by_tag_name(tag_name)
             };
-            child_elements.into_iter().map(|child| XmlNodeImpl(child))
+            child_elements.into_iter().map(XmlNodeImpl)
         }
 
         fn text(&self) -> Option<String> {
@@ -0,0 +1,50 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Helpers for rendering projects in tests without network access.
+
+use std::collections::HashMap;
+
+use templateer::versions::index::{VersionIndex, Versions};
+use templateer::versions::MinecraftVersionList;
+
+pub fn load_version_list() -> MinecraftVersionList {
+    serde_json::from_str(include_str!("../../src/minecraft_versions.json")).unwrap()
+}
+
+/// Creates a version index with fake versions of every dependency that exists for each game version.
+pub fn fake_version_index(version_list: &MinecraftVersionList) -> VersionIndex {
+    let versions: HashMap<String, Versions> = version_list
+        .versions
+        .iter()
+        .map(|game_version| {
+            let version = &game_version.version;
+            let neoforge = game_version.neoforge.as_ref();
+            let versions = Versions {
+                architectury_api: game_version.architectury.api_version.as_ref().map(|api| format!("{}.0.0", api)),
+                forge: game_version.forge.as_ref().map(|forge| format!("{}-{}.0.0", version, forge.major_version)),
+                neoforge: neoforge.map(|neoforge| format!("{}.0", neoforge.neoforge_major_version)),
+                neoforge_yarn_patch: neoforge
+                    .and_then(|neoforge| neoforge.yarn_patch_version.as_ref())
+                    .map(|patch| format!("{}+build.1", patch)),
+                fabric_loader: Some("0.16.0".to_owned()),
+                fabric_api: Some(format!("0.100.0+{}", version)),
+                yarn: Some(format!("{}+build.1", version)),
+                quilt_loader: Some("0.27.0".to_owned()),
+                quilted_fabric_api: Some(format!("7.0.0+0.100.0-{}", version)),
+                parchment: Some("2024.01.01".to_owned()),
+                moddevgradle: neoforge.map(|_| "2.0.100".to_owned()),
+                fabric_language_kotlin: Some("1.13.0+kotlin.2.1.0".to_owned()),
+                kotlin_for_forge: game_version.forge.as_ref()
+                    .and_then(|forge| forge.kotlin_for_forge_version.as_ref())
+                    .map(|major| format!("{}.0.0", major)),
+                kotlin_for_forge_neoforge: neoforge
+                    .and_then(|neoforge| neoforge.kotlin_for_forge_version.as_ref())
+                    .map(|major| format!("{}.0.0", major)),
+            };
+            (version.clone(), versions)
+        })
+        .collect();
+    VersionIndex { versions }
+}
@@ -0,0 +1,165 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Renders every supported combination of game version, project type, subprojects,
+//! mappings, language and Architectury API with fake dependency versions, and checks the output.
+
+mod common;
+
+use common::{fake_version_index, load_version_list};
+use strum::IntoEnumIterator;
+use templateer::app::generator;
+use templateer::templates::engine::RenderMode;
+use templateer::versions::index::VersionSource;
+use templateer::versions::{MinecraftVersion, MinecraftVersionList};
+use templateer::{Dependencies, GeneratorApp, Language, MappingSet, ProjectType, Subprojects, Toolchain};
+
+/// Lists the valid subproject combinations of multiplatform projects for a game version.
+fn subproject_combinations(game_version: &MinecraftVersion) -> Vec<Subprojects> {
+    let mut combinations = Vec::new();
+    for bits in 1..16u32 {
+        let subprojects = Subprojects {
+            fabric: bits & 1 != 0,
+            forge: bits & 2 != 0,
+            neoforge: bits & 4 != 0,
+            quilt: bits & 8 != 0,
+            fabric_likes: false,
+            forge_likes: false,
+        };
+        for fabric_likes in [false, true] {
+            for forge_likes in [false, true] {
+                let subprojects = Subprojects { fabric_likes, forge_likes, ..subprojects };
+                if subprojects.is_available_on(game_version) {
+                    combinations.push(subprojects);
+                }
+            }
+        }
+    }
+    combinations
+}
+
+fn all_apps(version_list: &MinecraftVersionList) -> Vec<GeneratorApp> {
+    let mut apps = Vec::new();
+    for language in Language::iter() {
+        let app = |game_version: &MinecraftVersion, project_type, subprojects, mapping_set, architectury_api| GeneratorApp {
+            mod_name: "Matrix Mod".to_owned(),
+            game_version: game_version.version.clone(),
+            project_type,
+            subprojects,
+            mapping_set,
+            language,
+            dependencies: Dependencies { architectury_api },
+            ..GeneratorApp::new(version_list)
+        };
+
+        for game_version in &version_list.versions {
+            for mapping_set in MappingSet::iter() {
+                for subprojects in subproject_combinations(game_version) {
+                    if subprojects.supports_architectury_api(game_version) {
+                        apps.push(app(game_version, ProjectType::Multiplatform, subprojects, mapping_set, true));
+                    }
+                    apps.push(app(game_version, ProjectType::Multiplatform, subprojects, mapping_set, false));
+                }
+
+                if game_version.forge.is_some() {
+                    apps.push(app(game_version, ProjectType::Forge, Subprojects::default(), mapping_set, false));
+                }
+                if game_version.neoforge.is_some() {
+                    for toolchain in Toolchain::iter().filter(|toolchain| toolchain.supports(mapping_set)) {
+                        apps.push(GeneratorApp {
+                            toolchain,
+                            ..app(game_version, ProjectType::NeoForge, Subprojects::default(), mapping_set, false)
+                        });
+                    }
+                }
+                apps.push(app(game_version, ProjectType::Fabric, Subprojects::default(), mapping_set, false));
+                apps.push(app(game_version, ProjectType::Quilt, Subprojects::default(), mapping_set, false));
+            }
+        }
+    }
+
+    apps
+}
+
+fn describe(app: &GeneratorApp) -> String {
+    let subprojects = &app.subprojects;
+    let loaders: Vec<&str> = [
+        (subprojects.fabric, "fabric"),
+        (subprojects.forge, "forge"),
+        (subprojects.neoforge, "neoforge"),
+        (subprojects.quilt, "quilt"),
+        (subprojects.fabric_likes, "fabric-like"),
+        (subprojects.forge_likes, "forge-like"),
+    ]
+    .into_iter()
+    .filter_map(|(enabled, name)| enabled.then_some(name))
+    .collect();
+    let project_type = match app.project_type {
+        ProjectType::Multiplatform => "multiplatform",
+        ProjectType::Forge => "forge",
+        ProjectType::NeoForge => "neoforge",
+        ProjectType::Fabric => "fabric",
+        ProjectType::Quilt => "quilt",
+    };
+    let mappings = match app.mapping_set {
+        MappingSet::Mojang => "mojang",
+        MappingSet::MojangParchment => "mojang-parchment",
+        MappingSet::Yarn => "yarn",
+    };
+    let language = match app.language {
+        Language::Java => "java",
+        Language::Kotlin => "kotlin",
+    };
+    let toolchain = match app.toolchain {
+        Toolchain::ArchitecturyLoom => "architectury-loom",
+        Toolchain::ModDevGradle => "moddevgradle",
+    };
+    format!(
+        "{} {} [{}] {} {} {} architectury_api={}",
+        app.game_version,
+        project_type,
+        loaders.join(","),
+        mappings,
+        language,
+        toolchain,
+        app.dependencies.architectury_api
+    )
+}
+
+/// Checks that the JSON and TOML files in the output can be parsed.
+fn check_syntax(path: &str, content: &[u8]) -> Result<(), String> {
+    let text = || std::str::from_utf8(content).map_err(|err| err.to_string());
+    if path.ends_with(".json") || path.ends_with(".mcmeta") {
+        serde_json::from_str::<serde_json::Value>(text()?).map_err(|err| err.to_string())?;
+    } else if path.ends_with(".toml") {
+        toml::from_str::<toml::Table>(text()?).map_err(|err| err.to_string())?;
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn render_all_combinations() {
+    let version_list = load_version_list();
+    let source = VersionSource::Offline(fake_version_index(&version_list));
+    let apps = all_apps(&version_list);
+    let mut failures = Vec::new();
+
+    for app in &apps {
+        let files = match generator::render(app, &version_list, &source, RenderMode::Strict).await {
+            Ok(files) => files,
+            Err(err) => {
+                failures.push(format!("{}: {}", describe(app), err));
+                continue;
+            }
+        };
+
+        for file in files {
+            if let Err(err) = check_syntax(&file.path, &file.content) {
+                failures.push(format!("{}: invalid {}: {}", describe(app), file.path, err));
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{} of {} combinations failed:\n{}", failures.len(), apps.len(), failures.join("\n"));
+}
@@ -0,0 +1,210 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Compares generated projects to the snapshots in `tests/snapshots`.
+//! Run with `UPDATE_SNAPSHOTS=1` to regenerate the snapshots after changing templates.
+
+mod common;
+
+use std::path::PathBuf;
+
+use common::{fake_version_index, load_version_list};
+use templateer::app::generator;
+use templateer::filer::{MemoryFiler, MemoryFilerProvider};
+use templateer::maven::MavenRepositoryUrls;
+use templateer::templates::engine::RenderMode;
+use templateer::versions::index::VersionSource;
+use templateer::versions::MinecraftVersionList;
+use templateer::{Dependencies, GeneratorApp, Language, MappingSet, ProjectType, Subprojects, Toolchain};
+
+/// The environment variable that makes the test write the snapshots instead of comparing them.
+const UPDATE_VARIABLE: &str = "UPDATE_SNAPSHOTS";
+
+/// The Gradle wrapper files from `src/templates/shared`, which are symlinks to the repository's own wrapper.
+/// They're only listed with their size, since their text depends on how the symlinks are checked out.
+const WRAPPER_FILES: [&str; 4] = [
+    "gradle/wrapper/gradle-wrapper.jar",
+    "gradle/wrapper/gradle-wrapper.properties",
+    "gradlew",
+    "gradlew.bat",
+];
+
+/// Returns the snapshot names and settings of the snapshotted projects.
+fn snapshot_apps(version_list: &MinecraftVersionList) -> Vec<(&'static str, GeneratorApp)> {
+    let app = |game_version: &str, project_type, subprojects, mapping_set, architectury_api| GeneratorApp {
+        mod_name: "Snapshot Mod".to_owned(),
+        package_name: "com.example.snapshot".to_owned(),
+        game_version: game_version.to_owned(),
+        project_type,
+        subprojects,
+        mapping_set,
+        dependencies: Dependencies { architectury_api },
+        ..GeneratorApp::new(version_list)
+    };
+
+    vec![
+        (
+            "multiplatform_1_21_1_fabric_neoforge",
+            app(
+                "1.21.1",
+                ProjectType::Multiplatform,
+                Subprojects { fabric: true, neoforge: true, ..Default::default() },
+                MappingSet::Mojang,
+                true,
+            ),
+        ),
+        (
+            "multiplatform_1_20_1_fabric_like_yarn",
+            app(
+                "1.20.1",
+                ProjectType::Multiplatform,
+                Subprojects { fabric: true, forge: true, quilt: true, fabric_likes: true, ..Default::default() },
+                MappingSet::Yarn,
+                true,
+            ),
+        ),
+        (
+            "multiplatform_1_20_4_forge_like",
+            app(
+                "1.20.4",
+                ProjectType::Multiplatform,
+                Subprojects { fabric: true, forge: true, neoforge: true, forge_likes: true, ..Default::default() },
+                MappingSet::Mojang,
+                false,
+            ),
+        ),
+        (
+            "multiplatform_1_20_1_kotlin",
+            GeneratorApp {
+                language: Language::Kotlin,
+                ..app(
+                    "1.20.1",
+                    ProjectType::Multiplatform,
+                    Subprojects { fabric: true, forge: true, quilt: true, ..Default::default() },
+                    MappingSet::Mojang,
+                    true,
+                )
+            },
+        ),
+        (
+            "multiplatform_1_20_1_quilt_mirror",
+            GeneratorApp {
+                repositories: MavenRepositoryUrls {
+                    quilt: vec!["https://mirror.example.com/quilt".to_owned()],
+                    ..Default::default()
+                },
+                ..app(
+                    "1.20.1",
+                    ProjectType::Multiplatform,
+                    Subprojects { fabric: true, quilt: true, ..Default::default() },
+                    MappingSet::Mojang,
+                    false,
+                )
+            },
+        ),
+        (
+            "multiplatform_1_16_5_fabric_forge",
+            app(
+                "1.16.5",
+                ProjectType::Multiplatform,
+                Subprojects { fabric: true, forge: true, ..Default::default() },
+                MappingSet::Mojang,
+                false,
+            ),
+        ),
+        ("fabric_1_21_1", app("1.21.1", ProjectType::Fabric, Subprojects::default(), MappingSet::Mojang, false)),
+        ("quilt_1_20_1_yarn", app("1.20.1", ProjectType::Quilt, Subprojects::default(), MappingSet::Yarn, false)),
+        ("forge_1_20_1", app("1.20.1", ProjectType::Forge, Subprojects::default(), MappingSet::Mojang, false)),
+        ("neoforge_1_20_4_yarn", app("1.20.4", ProjectType::NeoForge, Subprojects::default(), MappingSet::Yarn, false)),
+        (
+            "neoforge_1_21_1_parchment",
+            app("1.21.1", ProjectType::NeoForge, Subprojects::default(), MappingSet::MojangParchment, false),
+        ),
+        (
+            "neoforge_1_21_1_moddevgradle",
+            GeneratorApp {
+                toolchain: Toolchain::ModDevGradle,
+                ..app("1.21.1", ProjectType::NeoForge, Subprojects::default(), MappingSet::MojangParchment, false)
+            },
+        ),
+    ]
+}
+
+/// Writes every generated file with its permissions into one text.
+/// Binary and Gradle wrapper files are only listed with their size.
+fn render_snapshot(mut filer: MemoryFiler) -> String {
+    let mut snapshot = String::new();
+    filer.files.sort_by(|a, b| a.path.cmp(&b.path));
+    for file in filer.files {
+        let path = file.path;
+        let permissions = file.permissions.unix();
+        if WRAPPER_FILES.contains(&path.as_str()) {
+            snapshot += &format!("=== {} ({:o}, {} bytes)\n", path, permissions, file.content.len());
+            continue;
+        }
+        match String::from_utf8(file.content.to_vec()) {
+            Ok(text) => {
+                snapshot += &format!("=== {} ({:o})\n", path, permissions);
+                snapshot += &text;
+                if !text.ends_with('\n') {
+                    snapshot.push('\n');
+                }
+            }
+            Err(err) => {
+                snapshot += &format!("=== {} ({:o}, {} binary bytes)\n", path, permissions, err.as_bytes().len());
+            }
+        }
+    }
+    snapshot
+}
+
+#[tokio::test]
+async fn generated_projects_match_snapshots() {
+    let version_list = load_version_list();
+    let source = VersionSource::Offline(fake_version_index(&version_list));
+    let snapshot_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots");
+    let update = std::env::var_os(UPDATE_VARIABLE).is_some();
+    let mut mismatches = Vec::new();
+
+    for (name, app) in snapshot_apps(&version_list) {
+        let filer = MemoryFilerProvider::new();
+        generator::generate(&app, &version_list, &source, RenderMode::Strict, &filer).await.unwrap();
+        let snapshot = render_snapshot(filer.into_filer());
+        let path = snapshot_dir.join(format!("{}.snap", name));
+
+        if update {
+            std::fs::create_dir_all(&snapshot_dir).unwrap();
+            std::fs::write(&path, snapshot).unwrap();
+            continue;
+        }
+
+        let Ok(expected) = std::fs::read_to_string(&path) else {
+            mismatches.push(format!("{}: missing snapshot {}", name, path.display()));
+            continue;
+        };
+        if let Some((line_index, (expected, actual))) = expected
+            .lines()
+            .chain(std::iter::repeat(""))
+            .zip(snapshot.lines().chain(std::iter::repeat("")))
+            .take(expected.lines().count().max(snapshot.lines().count()))
+            .enumerate()
+            .find(|(_, (expected, actual))| expected != actual)
+        {
+            mismatches.push(format!(
+                "{}: line {} differs\n  expected: {}\n  actual:   {}",
+                name,
+                line_index + 1,
+                expected,
+                actual
+            ));
+        }
+    }
+
+    assert!(
+        mismatches.is_empty(),
+        "Generated projects differ from their snapshots. If this is intended, run `{}=1 cargo test --test snapshots`.\n{}",
+        UPDATE_VARIABLE,
+        mismatches.join("\n")
+    );
+}
@@ -3,6 +3,7 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use clap::Parser;
+use templateer::cli::CacheArgs;
 
 #[derive(Parser)]
 struct Cli {
@@ -10,14 +11,18 @@ struct Cli {
     version_list: std::path::PathBuf,
     #[arg(short, long, value_name = "FILE")]
     output: Option<std::path::PathBuf>,
+    #[command(flatten)]
+    cache: CacheArgs,
 }
 
 #[tokio::main]
 async fn main() -> templateer::Result<()> {
+    use templateer::maven::MavenRepositoryUrls;
     use templateer::versions::{index::VersionIndex, MinecraftVersionList};
 
     let cli = Cli::parse();
-    let client = reqwest::Client::new();
+    let client = cli.cache.create_client()?
+        .with_repositories(MavenRepositoryUrls::from_env()?);
     let list: MinecraftVersionList = serde_json::from_str(std::fs::read_to_string(cli.version_list)?.as_str())?;
     let index = VersionIndex::resolve(&client, &list).await?;
     let json = serde_json::to_string_pretty(&index)?;